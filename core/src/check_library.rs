@@ -6,7 +6,8 @@
 //! - LTT Labs MarkBench
 //! - Back2Gaming Windows tweaks guide
 
-use crate::config::{CheckConfig, CheckType};
+use crate::config::{CheckConfig, CheckKind, CheckType};
+use crate::probe::{Chassis, SystemProbe};
 
 /// Categories for organizing checks in the library UI
 pub const CATEGORIES: &[&str] = &[
@@ -14,7 +15,10 @@ pub const CATEGORIES: &[&str] = &[
     "Display Settings",
     "Windows Gaming Features",
     "Performance Registry Tweaks",
+    "Memory",
+    "Windows Update",
     "Background Apps",
+    "Scheduled Tasks",
     "RGB/Peripheral Software",
     "Monitoring Software",
     "Laptop-Specific",
@@ -31,6 +35,7 @@ pub struct LibraryCheck {
     pub registry_path: Option<&'static str>,
     pub registry_key: Option<&'static str>,
     pub process_name: Option<&'static str>,
+    pub task_name: Option<&'static str>,
     pub expected_value: Option<&'static str>,
     pub laptop_only: bool,
 }
@@ -38,16 +43,22 @@ pub struct LibraryCheck {
 impl LibraryCheck {
     /// Convert to a CheckConfig for adding to a scenario
     pub fn to_check_config(&self) -> CheckConfig {
-        CheckConfig {
-            id: self.id.to_string(),
-            name: self.name.to_string(),
-            check_type: self.check_type.clone(),
-            enabled: true,
-            registry_path: self.registry_path.map(|s| s.to_string()),
-            registry_key: self.registry_key.map(|s| s.to_string()),
-            process_name: self.process_name.map(|s| s.to_string()),
-            expected_value: self.expected_value.map(|s| s.to_string()),
-        }
+        let kind = CheckKind::from_fields(self.check_type, |field| {
+            match field {
+                "registry_path" => self.registry_path,
+                "registry_key" => self.registry_key,
+                "process_name" => self.process_name,
+                "task_name" => self.task_name,
+                "expected_value" => self.expected_value,
+                _ => None,
+            }
+            .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|e| panic!("library check '{}' is invalid: {}", self.id, e));
+
+        let mut check = CheckConfig::new(self.id, self.name, kind);
+        check.laptop_only = self.laptop_only;
+        check
     }
 }
 
@@ -64,6 +75,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("high_performance"),
             laptop_only: false,
         },
@@ -76,6 +88,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("ultimate_performance"),
             laptop_only: false,
         },
@@ -88,6 +101,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("balanced"),
             laptop_only: false,
         },
@@ -101,6 +115,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("best_performance"),
             laptop_only: false,
         },
@@ -113,6 +128,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("better_performance"),
             laptop_only: false,
         },
@@ -125,6 +141,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("balanced"),
             laptop_only: false,
         },
@@ -139,6 +156,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("3840x2160"),
             laptop_only: false,
         },
@@ -151,6 +169,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("2560x1440"),
             laptop_only: false,
         },
@@ -163,6 +182,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("1920x1080"),
             laptop_only: false,
         },
@@ -175,6 +195,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("1280x720"),
             laptop_only: false,
         },
@@ -187,6 +208,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("60"),
             laptop_only: false,
         },
@@ -199,6 +221,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("120"),
             laptop_only: false,
         },
@@ -211,6 +234,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("144"),
             laptop_only: false,
         },
@@ -223,6 +247,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("165"),
             laptop_only: false,
         },
@@ -235,6 +260,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("240"),
             laptop_only: false,
         },
@@ -247,9 +273,49 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("360"),
             laptop_only: false,
         },
+        LibraryCheck {
+            id: "display_10bit",
+            name: "10-bit Color Output",
+            category: "Display Settings",
+            description: "Primary display receives at least 10 bits per color channel",
+            check_type: CheckType::DisplayBitDepth,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("10"),
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "display_scaling_100",
+            name: "Display Scaling (100%)",
+            category: "Display Settings",
+            description: "No DPI scaling on the primary display, so captures are pixel-exact",
+            check_type: CheckType::DisplayScaling,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("100"),
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "display_landscape",
+            name: "Landscape Orientation",
+            category: "Display Settings",
+            description: "Primary display is not rotated",
+            check_type: CheckType::DisplayOrientation,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("landscape"),
+            laptop_only: false,
+        },
         LibraryCheck {
             id: "hdr_enabled",
             name: "HDR Enabled",
@@ -259,6 +325,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("1"),
             laptop_only: false,
         },
@@ -271,6 +338,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: None,
+            task_name: None,
             expected_value: Some("0"),
             laptop_only: false,
         },
@@ -285,6 +353,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKCU\\Software\\Microsoft\\GameBar"),
             registry_key: Some("AutoGameModeEnabled"),
             process_name: None,
+            task_name: None,
             expected_value: Some("1"),
             laptop_only: false,
         },
@@ -297,6 +366,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKCU\\Software\\Microsoft\\GameBar"),
             registry_key: Some("AutoGameModeEnabled"),
             process_name: None,
+            task_name: None,
             expected_value: Some("0"),
             laptop_only: false,
         },
@@ -309,6 +379,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKLM\\SYSTEM\\CurrentControlSet\\Control\\GraphicsDrivers"),
             registry_key: Some("HwSchMode"),
             process_name: None,
+            task_name: None,
             expected_value: Some("2"),
             laptop_only: false,
         },
@@ -321,6 +392,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKCU\\System\\GameConfigStore"),
             registry_key: Some("GameDVR_Enabled"),
             process_name: None,
+            task_name: None,
             expected_value: Some("0"),
             laptop_only: false,
         },
@@ -333,6 +405,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\BackgroundAccessApplications"),
             registry_key: Some("GlobalUserDisabled"),
             process_name: None,
+            task_name: None,
             expected_value: Some("1"),
             laptop_only: false,
         },
@@ -345,6 +418,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\VisualEffects"),
             registry_key: Some("VisualFXSetting"),
             process_name: None,
+            task_name: None,
             expected_value: Some("2"),
             laptop_only: false,
         },
@@ -359,6 +433,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile"),
             registry_key: Some("NetworkThrottlingIndex"),
             process_name: None,
+            task_name: None,
             expected_value: Some("4294967295"), // 0xFFFFFFFF
             laptop_only: false,
         },
@@ -371,6 +446,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile"),
             registry_key: Some("SystemResponsiveness"),
             process_name: None,
+            task_name: None,
             expected_value: Some("0"),
             laptop_only: false,
         },
@@ -383,11 +459,94 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\Multimedia\\SystemProfile\\Tasks\\Games"),
             registry_key: Some("GPU Priority"),
             process_name: None,
+            task_name: None,
             expected_value: Some("8"),
             laptop_only: false,
         },
 
+        // ===== Memory =====
+        LibraryCheck {
+            id: "memory_available",
+            name: "8 GB Memory Available",
+            category: "Memory",
+            description: "Low free memory lengthens load times and hurts 1% lows",
+            check_type: CheckType::MemoryAvailable,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("8"),
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "commit_charge",
+            name: "Commit Charge Below 80%",
+            category: "Memory",
+            description: "A high commit charge forces the pagefile to grow mid-run",
+            check_type: CheckType::CommitCharge,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("80"),
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "pagefile_fixed",
+            name: "Fixed Size Pagefile",
+            category: "Memory",
+            description: "A fixed pagefile never resizes during a run",
+            check_type: CheckType::Pagefile,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("fixed"),
+            laptop_only: false,
+        },
+
+        // ===== Windows Update =====
+        LibraryCheck {
+            id: "no_pending_reboot",
+            name: "No Pending Reboot",
+            category: "Windows Update",
+            description: "Pending updates and file renames finish in the background and cause stutters",
+            check_type: CheckType::PendingReboot,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("0"),
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "windows_update_idle",
+            name: "Windows Update Idle",
+            category: "Windows Update",
+            description: "Update downloads and installs load the CPU and disk during a run",
+            check_type: CheckType::WindowsUpdateActive,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("0"),
+            laptop_only: false,
+        },
+
         // ===== Background Apps (Process Absent) =====
+        LibraryCheck {
+            id: "cpu_idle",
+            name: "System Idle Before Run",
+            category: "Background Apps",
+            description: "Background CPU load above a few percent skews results; lists the busiest processes",
+            check_type: CheckType::CpuIdle,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("5"),
+            laptop_only: false,
+        },
         LibraryCheck {
             id: "no_discord",
             name: "Discord Not Running",
@@ -397,6 +556,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("Discord.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -409,6 +569,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("chrome.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -421,6 +582,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("firefox.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -433,6 +595,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("msedge.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -445,6 +608,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("Spotify.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -457,6 +621,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("ms-teams.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -469,6 +634,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("slack.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -481,6 +647,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("obs64.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -493,6 +660,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("wallpaper64.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -505,6 +673,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("steam.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -517,6 +686,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("EpicGamesLauncher.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -529,6 +699,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("GalaxyClient.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -541,6 +712,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("OneDrive.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -553,6 +725,100 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("Dropbox.exe"),
+            task_name: None,
+            expected_value: None,
+            laptop_only: false,
+        },
+
+        // ===== Scheduled Tasks =====
+        LibraryCheck {
+            id: "task_defrag_off",
+            name: "Scheduled Defrag Disabled",
+            category: "Scheduled Tasks",
+            description: "Drive optimization runs on a schedule and hammers disk I/O",
+            check_type: CheckType::TaskDisabled,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: Some("\\Microsoft\\Windows\\Defrag\\ScheduledDefrag"),
+            expected_value: None,
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "task_defender_scan_off",
+            name: "Defender Scheduled Scan Disabled",
+            category: "Scheduled Tasks",
+            description: "Scheduled malware scans compete for CPU and disk",
+            check_type: CheckType::TaskDisabled,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: Some("\\Microsoft\\Windows\\Windows Defender\\Windows Defender Scheduled Scan"),
+            expected_value: None,
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "task_compat_appraiser_off",
+            name: "Compatibility Appraiser Disabled",
+            category: "Scheduled Tasks",
+            description: "Telemetry task known for long CPU-heavy runs",
+            check_type: CheckType::TaskDisabled,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: Some("\\Microsoft\\Windows\\Application Experience\\Microsoft Compatibility Appraiser"),
+            expected_value: None,
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "task_ceip_off",
+            name: "CEIP Consolidator Disabled",
+            category: "Scheduled Tasks",
+            description: "Customer Experience Improvement Program upload task",
+            check_type: CheckType::TaskDisabled,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: Some("\\Microsoft\\Windows\\Customer Experience Improvement Program\\Consolidator"),
+            expected_value: None,
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "task_disk_cleanup_idle",
+            name: "Disk Cleanup Not Running",
+            category: "Scheduled Tasks",
+            description: "Silent disk cleanup scans the drive in the background",
+            check_type: CheckType::TaskNotRunning,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: Some("\\Microsoft\\Windows\\DiskCleanup\\SilentCleanup"),
+            expected_value: None,
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "task_update_scan_idle",
+            name: "Update Scan Not Running",
+            category: "Scheduled Tasks",
+            description: "Windows Update scans cause CPU and disk spikes",
+            check_type: CheckType::TaskNotRunning,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: Some("\\Microsoft\\Windows\\UpdateOrchestrator\\Schedule Scan"),
+            expected_value: None,
+            laptop_only: false,
+        },
+        LibraryCheck {
+            id: "task_edge_update_idle",
+            name: "Edge Updater Not Running",
+            category: "Scheduled Tasks",
+            description: "Vendor updater that fires on a schedule",
+            check_type: CheckType::TaskNotRunning,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: Some("\\MicrosoftEdgeUpdateTaskMachineUA"),
             expected_value: None,
             laptop_only: false,
         },
@@ -567,6 +833,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("Razer Synapse 3.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -579,6 +846,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("iCUE.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -591,6 +859,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("ArmouryCrate.UserSessionHelper.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -603,6 +872,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("NZXT CAM.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -615,6 +885,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("SignalRgb.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -627,6 +898,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("lghub.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -639,6 +911,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("SteelSeriesGG.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -653,6 +926,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("MSIAfterburner.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -665,6 +939,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("HWiNFO64.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -677,6 +952,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("obs64.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -689,6 +965,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: None,
             registry_key: None,
             process_name: Some("vrserver.exe"),
+            task_name: None,
             expected_value: None,
             laptop_only: false,
         },
@@ -703,6 +980,7 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKCU\\Software\\NVIDIA Corporation\\Global\\Battery Boost"),
             registry_key: Some("EnableBatteryBoost"),
             process_name: None,
+            task_name: None,
             expected_value: Some("0"),
             laptop_only: true,
         },
@@ -715,17 +993,64 @@ pub fn get_library() -> Vec<LibraryCheck> {
             registry_path: Some("HKCU\\Software\\NVIDIA Corporation\\Global\\Whisper Mode"),
             registry_key: Some("EnableWhisperMode"),
             process_name: None,
+            task_name: None,
             expected_value: Some("0"),
             laptop_only: true,
         },
+        LibraryCheck {
+            id: "laptop_ac_power",
+            name: "Laptop Plugged In",
+            category: "Laptop-Specific",
+            description: "Laptops throttle CPU and GPU on battery power",
+            check_type: CheckType::AcPower,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("1"),
+            laptop_only: true,
+        },
+        LibraryCheck {
+            id: "laptop_battery_level",
+            name: "Battery Charged",
+            category: "Laptop-Specific",
+            description: "Some laptops limit power while the battery is charging from low",
+            check_type: CheckType::BatteryLevel,
+            registry_path: None,
+            registry_key: None,
+            process_name: None,
+            task_name: None,
+            expected_value: Some("80"),
+            laptop_only: true,
+        },
     ]
 }
 
+/// Get the checks that apply to this machine (laptop-only checks are hidden
+/// on desktops; shown if the chassis can't be determined)
+pub fn get_library_for(probe: &dyn SystemProbe) -> Vec<LibraryCheck> {
+    let desktop = probe.chassis() == Ok(Chassis::Desktop);
+    get_library().into_iter().filter(|c| !(desktop && c.laptop_only)).collect()
+}
+
 /// Get checks filtered by category
-#[allow(dead_code)]
 pub fn get_checks_by_category(category: &str) -> Vec<LibraryCheck> {
     get_library()
         .into_iter()
         .filter(|c| c.category == category)
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_library_checks_are_valid() {
+        for lc in get_library() {
+            let check = lc.to_check_config();
+            assert_eq!(check.check_type(), lc.check_type);
+            assert!(check.validate().is_empty(), "{}: {:?}", lc.id, check.validate());
+        }
+    }
+}
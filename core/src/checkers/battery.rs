@@ -0,0 +1,47 @@
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, ValueCheck};
+use crate::probe::SystemProbe;
+
+/// Check whether the machine is on AC power: "1" for plugged in, "0" for on battery
+pub fn check_ac_power(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = &check.expected_value;
+    let expected_display = expected.describe(&|v| if v == "1" { "Plugged in" } else { "On battery" }.to_string());
+
+    match probe.power_status() {
+        Ok(status) => match status.ac_online {
+            Some(online) => {
+                let current = if online { "Plugged in" } else { "On battery" };
+                if expected.matches(if online { "1" } else { "0" }) {
+                    CheckResult::pass(&config.id, &config.name, current, &expected_display)
+                } else {
+                    CheckResult::fail(&config.id, &config.name, current, &expected_display)
+                }
+            }
+            None => CheckResult::error(&config.id, &config.name, "AC line status is unknown"),
+        },
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check the battery charge (a bare number like "80" is a minimum)
+///
+/// Machines without a battery are skipped rather than failed.
+pub fn check_battery_level(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = check.expected_value.bare_as_minimum();
+
+    match probe.power_status() {
+        Ok(status) => match status.battery_percent {
+            Some(percent) => {
+                let current = format!("{}%", percent);
+                let expected_display = expected.describe(&|v| format!("{}%", v));
+                if expected.matches(&percent.to_string()) {
+                    CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+                } else {
+                    CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+                }
+            }
+            None => CheckResult::skip(&config.id, &config.name, "no battery present"),
+        },
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
@@ -0,0 +1,90 @@
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, DiskFreeCheck, DriveTypeCheck};
+use crate::probe::{DriveKind, SystemProbe};
+use serde::{Deserialize, Serialize};
+
+const GB: f64 = (1u64 << 30) as f64;
+
+/// What a disk_free check's expected value counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FreeSpaceUnit {
+    #[default]
+    Gb,
+    /// Percentage of the volume's size
+    Percent,
+}
+
+impl FreeSpaceUnit {
+    pub fn is_gb(&self) -> bool {
+        *self == FreeSpaceUnit::Gb
+    }
+}
+
+/// Parse a drive kind as written in the config (case-insensitive)
+pub fn parse_drive_kind(s: &str) -> Option<DriveKind> {
+    serde_json::from_value(serde_json::Value::String(s.trim().to_lowercase())).ok()
+}
+
+/// Whether a drive is of the expected kind; NVMe drives count as SSDs
+fn is_kind(actual: DriveKind, expected: DriveKind) -> bool {
+    actual == expected || (expected == DriveKind::Ssd && actual == DriveKind::Nvme)
+}
+
+/// Check free space on a path's volume (a bare number like "100" is a minimum)
+pub fn check_free(probe: &dyn SystemProbe, config: &CheckConfig, check: &DiskFreeCheck) -> CheckResult {
+    let expected = check.expected_value.bare_as_minimum();
+    let suffix = match check.unit {
+        FreeSpaceUnit::Gb => " GB",
+        FreeSpaceUnit::Percent => "%",
+    };
+    let expected_display = expected.describe(&|v| format!("{}{} free", v, suffix));
+
+    match probe.disk_space(&check.path) {
+        Ok(space) => {
+            let free_gb = space.free_bytes as f64 / GB;
+            let free_percent = match space.total_bytes {
+                0 => 0.0,
+                total => 100.0 * space.free_bytes as f64 / total as f64,
+            };
+            let current = format!(
+                "{:.1} GB free of {:.1} GB ({:.0}%) on {}",
+                free_gb,
+                space.total_bytes as f64 / GB,
+                free_percent,
+                space.volume
+            );
+            let value = match check.unit {
+                FreeSpaceUnit::Gb => free_gb,
+                FreeSpaceUnit::Percent => free_percent,
+            };
+            if expected.matches(&format!("{:.1}", value)) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check the kind of drive a path lives on (`nvme`, `ssd`, `hdd`, `removable`
+/// or `network`; `ssd` also accepts NVMe drives)
+pub fn check_drive_type(probe: &dyn SystemProbe, config: &CheckConfig, check: &DriveTypeCheck) -> CheckResult {
+    let expected = &check.expected_value;
+    let expected_display =
+        expected.describe(&|v| parse_drive_kind(v).map(|k| k.to_string()).unwrap_or_else(|| v.to_string()));
+
+    match probe.drive_kind(&check.path) {
+        Ok(kind) => {
+            let current = format!("{} ({})", kind, check.path);
+            let same = |e: &str, _: &str| parse_drive_kind(e).is_some_and(|e| is_kind(kind, e));
+            if expected.matches_by(kind.as_str(), &same) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
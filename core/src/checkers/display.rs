@@ -0,0 +1,245 @@
+//! Display settings checks for resolution, refresh rate, color depth, scaling,
+//! orientation, HDR state and the number of active displays
+
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, DisplayCheck, ValueCheck};
+use crate::probe::{DisplayInfo, DisplayOrientation, SystemProbe};
+use serde::{Deserialize, Serialize};
+
+/// Which monitor a display check looks at (stored in the config as text)
+///
+/// - `primary` (or omitted): the primary display
+/// - `\\.\DISPLAY2` or `DISPLAY2`: a GDI device name
+/// - anything else: the monitor model reported by its EDID, e.g. `DELL P2419H`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum DisplayTarget {
+    Primary,
+    Device(String),
+    Model(String),
+}
+
+impl DisplayTarget {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err("display is empty".to_string());
+        }
+        if text.eq_ignore_ascii_case("primary") {
+            return Ok(Self::Primary);
+        }
+
+        let device = text.strip_prefix("\\\\.\\").unwrap_or(text);
+        let is_device = match (device.get(..7), device.get(7..)) {
+            (Some(prefix), Some(number)) => {
+                prefix.eq_ignore_ascii_case("DISPLAY")
+                    && !number.is_empty()
+                    && number.bytes().all(|b| b.is_ascii_digit())
+            }
+            _ => false,
+        };
+        if is_device {
+            Ok(Self::Device(format!("\\\\.\\{}", device.to_ascii_uppercase())))
+        } else {
+            Ok(Self::Model(text.to_string()))
+        }
+    }
+
+    /// The target as written in the config
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Primary => "primary",
+            Self::Device(name) | Self::Model(name) => name,
+        }
+    }
+
+    pub fn matches(&self, display: &DisplayInfo) -> bool {
+        match self {
+            Self::Primary => display.primary,
+            Self::Device(name) => display.device_name.eq_ignore_ascii_case(name),
+            Self::Model(model) => display.model.eq_ignore_ascii_case(model),
+        }
+    }
+}
+
+impl TryFrom<String> for DisplayTarget {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        Self::parse(&s)
+    }
+}
+
+impl From<DisplayTarget> for String {
+    fn from(target: DisplayTarget) -> String {
+        target.as_str().to_string()
+    }
+}
+
+/// Find the display a check targets (the primary one when none is given)
+pub fn find_display(probe: &dyn SystemProbe, target: Option<&DisplayTarget>) -> Result<DisplayInfo, String> {
+    let target = target.unwrap_or(&DisplayTarget::Primary);
+    probe
+        .displays()?
+        .into_iter()
+        .find(|d| target.matches(d))
+        .ok_or_else(|| format!("No active display matches '{}'", target.as_str()))
+}
+
+/// Check display resolution against expected (e.g., "3840x2160")
+pub fn check_resolution(probe: &dyn SystemProbe, config: &CheckConfig, check: &DisplayCheck) -> CheckResult {
+    let expected = &check.expected_value;
+
+    match find_display(probe, check.display.as_ref()) {
+        Ok(DisplayInfo { mode, .. }) => {
+            let current = format!("{}x{}", mode.width, mode.height);
+            if expected.matches(&current) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected.to_string())
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected.to_string())
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check refresh rate against expected (a bare number like "144" is a minimum)
+pub fn check_refresh_rate(probe: &dyn SystemProbe, config: &CheckConfig, check: &DisplayCheck) -> CheckResult {
+    let expected = check.expected_value.bare_as_minimum();
+
+    match find_display(probe, check.display.as_ref()) {
+        Ok(DisplayInfo { mode, .. }) => {
+            let hz = mode.refresh_hz;
+            let current = format!("{}Hz", hz);
+            let expected_display = expected.describe(&|v| format!("{}Hz", v));
+            if expected.matches(&hz.to_string()) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check bits per color channel (a bare number like "10" is a minimum)
+pub fn check_bit_depth(probe: &dyn SystemProbe, config: &CheckConfig, check: &DisplayCheck) -> CheckResult {
+    let expected = check.expected_value.bare_as_minimum();
+
+    match find_display(probe, check.display.as_ref()) {
+        Ok(display) => {
+            let bits = display.bits_per_channel;
+            let current = format!("{}-bit", bits);
+            let expected_display = expected.describe(&|v| format!("{}-bit", v));
+            if expected.matches(&bits.to_string()) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check DPI scaling in percent (e.g., "100")
+pub fn check_scaling(probe: &dyn SystemProbe, config: &CheckConfig, check: &DisplayCheck) -> CheckResult {
+    let expected = &check.expected_value;
+
+    match find_display(probe, check.display.as_ref()) {
+        Ok(display) => {
+            let scale = display.scale_percent;
+            let current = format!("{}%", scale);
+            let expected_display = expected.describe(&|v| format!("{}%", v));
+            if expected.matches(&scale.to_string()) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check display rotation (`landscape`, `portrait`, `landscape_flipped`, `portrait_flipped`)
+pub fn check_orientation(probe: &dyn SystemProbe, config: &CheckConfig, check: &DisplayCheck) -> CheckResult {
+    let expected = &check.expected_value;
+    let expected_display =
+        expected.describe(&|v| parse_orientation(v).map(|o| o.to_string()).unwrap_or_else(|| v.to_string()));
+
+    match find_display(probe, check.display.as_ref()) {
+        Ok(display) => {
+            let current = display.orientation.to_string();
+            let same = |e: &str, _: &str| parse_orientation(e) == Some(display.orientation);
+            if expected.matches_by(display.orientation.as_str(), &same) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Parse an orientation as written in the config (case-insensitive)
+pub fn parse_orientation(s: &str) -> Option<DisplayOrientation> {
+    serde_json::from_value(serde_json::Value::String(s.trim().to_lowercase())).ok()
+}
+
+/// Check the number of active displays
+pub fn check_display_count(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = &check.expected_value;
+
+    match probe.displays() {
+        Ok(displays) => {
+            let names: Vec<String> = displays.iter().map(display_label).collect();
+            let current = format!("{} active: {}", displays.len(), names.join("; "));
+            if expected.matches(&displays.len().to_string()) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected.to_string())
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected.to_string())
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check a display's HDR (advanced color) state: "1" for on, "0" for off
+///
+/// The current value names the monitor, since HDR is set per display.
+pub fn check_hdr(probe: &dyn SystemProbe, config: &CheckConfig, check: &DisplayCheck) -> CheckResult {
+    let expected = &check.expected_value;
+    let expected_display = expected.describe(&|v| if v == "1" { "Enabled" } else { "Disabled" }.to_string());
+
+    match find_display(probe, check.display.as_ref()) {
+        Ok(display) => {
+            let state = match (display.hdr_supported, display.hdr_enabled) {
+                (_, true) => "Enabled",
+                (true, false) => "Disabled",
+                (false, false) => "Not supported",
+            };
+            let current = format!("{} ({})", state, display_label(&display));
+            if expected.matches(if display.hdr_enabled { "1" } else { "0" }) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Device name followed by the monitor model, when known
+fn display_label(display: &DisplayInfo) -> String {
+    match display.model.as_str() {
+        "" => display.device_name.clone(),
+        model => format!("{} {}", display.device_name, model),
+    }
+}
+
+/// Get the primary display's mode as a formatted string (for UI display)
+pub fn get_display_info(probe: &dyn SystemProbe) -> String {
+    match find_display(probe, None) {
+        Ok(DisplayInfo { mode, .. }) => mode.to_string(),
+        Err(_) => "Unknown".to_string(),
+    }
+}
@@ -0,0 +1,403 @@
+//! Expected-value expressions
+//!
+//! A check's `expected_value` is either a plain value (exact match) or an
+//! expression:
+//!
+//! - `>=120`, `>120`, `<=5`, `<5`: numeric comparison
+//! - `1..=3` (inclusive) or `1..3` (end excluded): numeric range
+//! - `one_of: [1, 2]`: any of the listed values
+//! - `not: 0`: anything except the value (or expression)
+//! - `regex: ^Intel.*`: regular expression match against the value's text
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Numeric comparison operator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+impl Comparison {
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparison::Greater => ">",
+            Comparison::GreaterOrEqual => ">=",
+            Comparison::Less => "<",
+            Comparison::LessOrEqual => "<=",
+        }
+    }
+}
+
+/// A number in an expression; integers (including QWORDs and hex masks)
+/// compare exactly, anything with a fraction compares as `f64`
+#[derive(Debug, Clone, Copy)]
+pub enum Number {
+    Integer(i128),
+    Fraction(f64),
+}
+
+impl Number {
+    fn as_f64(self) -> f64 {
+        match self {
+            Number::Integer(n) => n as f64,
+            Number::Fraction(n) => n,
+        }
+    }
+}
+
+impl PartialEq for Number {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Number {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Number::Integer(a), Number::Integer(b)) => Some(a.cmp(b)),
+            _ => self.as_f64().partial_cmp(&other.as_f64()),
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Integer(n) => write!(f, "{}", n),
+            Number::Fraction(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// A parsed expected value (stored in the config as its expression text)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Expectation {
+    Equals(String),
+    Compare(Comparison, Number),
+    Range { start: Number, end: Number, inclusive: bool },
+    OneOf(Vec<String>),
+    Not(Box<Expectation>),
+    Regex(Regex),
+}
+
+impl Expectation {
+    /// Parse an expected-value expression
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let s = s.trim();
+
+        if let Some(rest) = strip_keyword(s, "one_of") {
+            let list = rest
+                .strip_prefix('[')
+                .and_then(|r| r.strip_suffix(']'))
+                .ok_or_else(|| format!("one_of needs a list like [1, 2], got '{}'", rest))?;
+            let values: Vec<String> = list
+                .split(',')
+                .map(|v| unquote(v.trim()).to_string())
+                .filter(|v| !v.is_empty())
+                .collect();
+            if values.is_empty() {
+                return Err("one_of list is empty".to_string());
+            }
+            return Ok(Expectation::OneOf(values));
+        }
+
+        if let Some(rest) = strip_keyword(s, "not") {
+            return Ok(Expectation::Not(Box::new(Self::parse(rest)?)));
+        }
+
+        if let Some(rest) = strip_keyword(s, "regex") {
+            return Regex::new(rest)
+                .map(Expectation::Regex)
+                .map_err(|e| format!("invalid regex '{}': {}", rest, e));
+        }
+
+        if let Some(rest) = s.strip_prefix("!=") {
+            return Ok(Expectation::Not(Box::new(Expectation::Equals(rest.trim().to_string()))));
+        }
+        if let Some(rest) = s.strip_prefix("==") {
+            return Ok(Expectation::Equals(rest.trim().to_string()));
+        }
+
+        // Two-character operators first so ">=" isn't read as ">"
+        let comparisons = [
+            (">=", Comparison::GreaterOrEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+        ];
+        for (symbol, comparison) in comparisons {
+            if let Some(rest) = s.strip_prefix(symbol) {
+                let n = number(rest)
+                    .ok_or_else(|| format!("'{}' needs a number, got '{}'", symbol, rest.trim()))?;
+                return Ok(Expectation::Compare(comparison, n));
+            }
+        }
+
+        if let Some(range) = parse_range(s) {
+            return range;
+        }
+
+        Ok(Expectation::Equals(s.to_string()))
+    }
+
+    /// Read a bare number as a minimum ("144" means ">=144"), as refresh rate checks do
+    pub fn bare_as_minimum(&self) -> Expectation {
+        match self {
+            Expectation::Equals(v) => match number(v) {
+                Some(n) => Expectation::Compare(Comparison::GreaterOrEqual, n),
+                None => self.clone(),
+            },
+            other => other.clone(),
+        }
+    }
+
+    /// Read a bare number as a maximum ("5" means "<=5"), as load checks do
+    pub fn bare_as_maximum(&self) -> Expectation {
+        match self {
+            Expectation::Equals(v) => match number(v) {
+                Some(n) => Expectation::Compare(Comparison::LessOrEqual, n),
+                None => self.clone(),
+            },
+            other => other.clone(),
+        }
+    }
+
+    /// The expression in config syntax (what `parse` reads back)
+    pub fn expression(&self) -> String {
+        match self {
+            Expectation::Equals(v) => v.clone(),
+            Expectation::Compare(comparison, n) => format!("{}{}", comparison.symbol(), n),
+            Expectation::Range { start, end, inclusive } => {
+                format!("{}{}{}", start, if *inclusive { "..=" } else { ".." }, end)
+            }
+            Expectation::OneOf(values) => format!("one_of: [{}]", values.join(", ")),
+            Expectation::Not(inner) => format!("not: {}", inner.expression()),
+            Expectation::Regex(re) => format!("regex: {}", re.as_str()),
+        }
+    }
+
+    /// Whether a value satisfies this expectation
+    pub fn matches(&self, current: &str) -> bool {
+        self.matches_by(current, &values_equal)
+    }
+
+    /// Like `matches`, with a custom equality for literal values (e.g. aliases)
+    pub fn matches_by<F>(&self, current: &str, equals: &F) -> bool
+    where
+        F: Fn(&str, &str) -> bool,
+    {
+        match self {
+            Expectation::Equals(expected) => equals(expected, current),
+            Expectation::OneOf(values) => values.iter().any(|v| equals(v, current)),
+            Expectation::Not(inner) => !inner.matches_by(current, equals),
+            Expectation::Regex(re) => re.is_match(current),
+            Expectation::Compare(comparison, n) => number(current).is_some_and(|c| match comparison {
+                Comparison::Greater => c > *n,
+                Comparison::GreaterOrEqual => c >= *n,
+                Comparison::Less => c < *n,
+                Comparison::LessOrEqual => c <= *n,
+            }),
+            Expectation::Range { start, end, inclusive } => number(current)
+                .is_some_and(|c| c >= *start && if *inclusive { c <= *end } else { c < *end }),
+        }
+    }
+
+    /// Literal values named in the expression (for validating them per check type)
+    pub fn literals(&self) -> Vec<&str> {
+        match self {
+            Expectation::Equals(v) => vec![v.as_str()],
+            Expectation::OneOf(values) => values.iter().map(|v| v.as_str()).collect(),
+            Expectation::Not(inner) => inner.literals(),
+            Expectation::Compare(..) | Expectation::Range { .. } | Expectation::Regex(_) => vec![],
+        }
+    }
+
+    /// Whether the expression compares numerically (`>=`, ranges)
+    pub fn is_ordered(&self) -> bool {
+        match self {
+            Expectation::Compare(..) | Expectation::Range { .. } => true,
+            Expectation::Not(inner) => inner.is_ordered(),
+            _ => false,
+        }
+    }
+
+    /// A single value that satisfies the expression, for fixers to set
+    pub fn fix_target(&self) -> Option<String> {
+        match self {
+            Expectation::Equals(v) => Some(v.clone()),
+            Expectation::OneOf(values) => values.first().cloned(),
+            Expectation::Compare(Comparison::GreaterOrEqual | Comparison::LessOrEqual, n) => {
+                Some(n.to_string())
+            }
+            Expectation::Range { start, end, inclusive } if *inclusive || start < end => {
+                Some(start.to_string())
+            }
+            _ => None,
+        }
+    }
+
+    /// Render the expression, formatting each value with `value` (e.g. to add units)
+    pub fn describe(&self, value: &dyn Fn(&str) -> String) -> String {
+        match self {
+            Expectation::Equals(v) => value(v),
+            Expectation::Compare(comparison, n) => {
+                format!("{} {}", comparison.symbol(), value(&n.to_string()))
+            }
+            Expectation::Range { start, end, inclusive } => format!(
+                "{}{}{}",
+                value(&start.to_string()),
+                if *inclusive { "..=" } else { ".." },
+                value(&end.to_string())
+            ),
+            Expectation::OneOf(values) => format!(
+                "one of {}",
+                values.iter().map(|v| value(v)).collect::<Vec<_>>().join(", ")
+            ),
+            Expectation::Not(inner) => format!("not {}", inner.describe(value)),
+            Expectation::Regex(re) => format!("matches /{}/", re.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for Expectation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.describe(&|v| v.to_string()))
+    }
+}
+
+impl TryFrom<String> for Expectation {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        Self::parse(&s)
+    }
+}
+
+impl From<Expectation> for String {
+    fn from(expectation: Expectation) -> String {
+        expectation.expression()
+    }
+}
+
+/// Strip `keyword:` from the start of an expression
+fn strip_keyword<'a>(s: &'a str, keyword: &str) -> Option<&'a str> {
+    s.strip_prefix(keyword)?
+        .trim_start()
+        .strip_prefix(':')
+        .map(str::trim)
+}
+
+fn unquote(s: &str) -> &str {
+    s.strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(s)
+}
+
+/// Finite decimal number, or `0x`-prefixed hex integer (registry masks)
+fn number(s: &str) -> Option<Number> {
+    let s = s.trim();
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok().map(|v| Number::Integer(v.into())),
+        None => match s.parse() {
+            Ok(n) => Some(Number::Integer(n)),
+            Err(_) => s.parse().ok().filter(|f: &f64| f.is_finite()).map(Number::Fraction),
+        },
+    }
+}
+
+/// Parse `a..=b` or `a..b`; None if the text has no `..`
+fn parse_range(s: &str) -> Option<Result<Expectation, String>> {
+    let (start, rest) = s.split_once("..")?;
+    let (end, inclusive) = match rest.strip_prefix('=') {
+        Some(end) => (end, true),
+        None => (rest, false),
+    };
+    let (Some(start), Some(end)) = (number(start), number(end)) else {
+        return Some(Err("range bounds must be finite numbers".to_string()));
+    };
+
+    if start > end {
+        return Some(Err(format!("range start {} is greater than end {}", start, end)));
+    }
+    Some(Ok(Expectation::Range { start, end, inclusive }))
+}
+
+/// Exact text match, or numeric equality when both sides are numbers ("01" == "1")
+fn values_equal(expected: &str, current: &str) -> bool {
+    expected == current
+        || matches!((number(expected), number(current)), (Some(a), Some(b)) if a == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Expectation {
+        Expectation::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_match() {
+        assert!(parse(">=120").matches("144"));
+        assert!(!parse(">=120").matches("60"));
+        assert!(parse("<5").matches("4.5"));
+        assert!(parse("1..=3").matches("3"));
+        assert!(!parse("1..3").matches("3"));
+        assert!(parse("one_of: [1, 2]").matches("2"));
+        assert!(!parse("one_of: [1, 2]").matches("0"));
+        assert!(parse("not: 0").matches("1"));
+        assert!(!parse("not: 0").matches("0"));
+        assert!(parse("regex: ^Intel.*").matches("Intel(R) Iris"));
+        assert!(parse("2").matches("2"));
+        assert!(!parse(">=120").matches("fast"));
+
+        assert!(Expectation::parse(">=fast").is_err());
+        assert!(Expectation::parse("one_of: []").is_err());
+        assert!(Expectation::parse("regex: (").is_err());
+        assert!(Expectation::parse("5..1").is_err());
+
+        // Infinities and NaN aren't numbers to compare against
+        assert!(Expectation::parse(">=inf").is_err());
+        assert!(Expectation::parse("0..=infinity").is_err());
+        assert!(Expectation::parse("1..5x").is_err());
+        assert!(!parse(">=0").matches("NaN"));
+        assert!(!parse("<5").matches("-inf"));
+    }
+
+    #[test]
+    fn test_large_integers_compare_exactly() {
+        // 2^53 + 1 is the first integer f64 can't hold
+        assert!(parse(">9007199254740992").matches("9007199254740993"));
+        assert!(!parse("9007199254740992").matches("9007199254740993"));
+        assert!(!parse("<=0xFFFFFFFFFFFFFFFE").matches("18446744073709551615"));
+        assert!(parse("0..=0xFFFFFFFFFFFFFFFF").matches("18446744073709551615"));
+        assert!(parse(">=-9223372036854775808").matches("-9223372036854775807"));
+        assert!(parse("<1.5").matches("1"));
+        assert_eq!(parse(">=18446744073709551615").fix_target().as_deref(), Some("18446744073709551615"));
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(parse(">=120").to_string(), ">= 120");
+        assert_eq!(parse("1..=3").to_string(), "1..=3");
+        assert_eq!(parse("one_of: [1, \"2\"]").to_string(), "one of 1, 2");
+        assert_eq!(parse("not: 0").to_string(), "not 0");
+        assert_eq!(parse("3840x2160").to_string(), "3840x2160");
+        assert_eq!(
+            parse("144").bare_as_minimum().describe(&|v| format!("{}Hz", v)),
+            ">= 144Hz"
+        );
+
+        for expression in [">=120", "1..=3", "one_of: [1, 2]", "not: regex: ^a"] {
+            assert_eq!(parse(expression).expression(), expression);
+        }
+    }
+}
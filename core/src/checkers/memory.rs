@@ -0,0 +1,154 @@
+use crate::checkers::registry;
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, ValueCheck};
+use crate::probe::{RegistryValue, RegistryView, SystemProbe, KEY_NOT_FOUND, VALUE_NOT_FOUND};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Where Windows keeps the pagefile configuration
+pub const MEMORY_MANAGEMENT_KEY: &str = "HKLM\\SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management";
+
+const GB: f64 = (1u64 << 30) as f64;
+
+/// How the pagefiles are sized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PagefileMode {
+    /// Windows grows and shrinks the pagefile as needed
+    SystemManaged,
+    /// Initial and maximum size are equal, so it never resizes
+    Fixed,
+    /// Initial and maximum size differ, or drives are set up differently
+    Custom,
+    None,
+}
+
+impl PagefileMode {
+    /// Name as written in the config
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PagefileMode::SystemManaged => "system_managed",
+            PagefileMode::Fixed => "fixed",
+            PagefileMode::Custom => "custom",
+            PagefileMode::None => "none",
+        }
+    }
+}
+
+impl fmt::Display for PagefileMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PagefileMode::SystemManaged => "System managed",
+            PagefileMode::Fixed => "Fixed size",
+            PagefileMode::Custom => "Custom size",
+            PagefileMode::None => "No pagefile",
+        })
+    }
+}
+
+/// Parse a pagefile mode as written in the config (case-insensitive)
+pub fn parse_pagefile_mode(s: &str) -> Option<PagefileMode> {
+    serde_json::from_value(serde_json::Value::String(s.trim().to_lowercase())).ok()
+}
+
+/// Mode of one `PagingFiles` entry: `?:\pagefile.sys` (all drives managed),
+/// `C:\pagefile.sys 0 0` (system managed on C:) or `C:\pagefile.sys 4096 8192` (MB)
+fn entry_mode(entry: &str) -> PagefileMode {
+    let mut parts = entry.split_whitespace().skip(1);
+    let size = |part: Option<&str>| part.and_then(|p| p.parse::<u64>().ok()).unwrap_or(0);
+    match (size(parts.next()), size(parts.next())) {
+        (0, 0) => PagefileMode::SystemManaged,
+        (min, max) if min == max => PagefileMode::Fixed,
+        _ => PagefileMode::Custom,
+    }
+}
+
+/// Overall mode of a `PagingFiles` list
+pub fn pagefile_mode(entries: &[String]) -> PagefileMode {
+    let mut modes = entries.iter().filter(|e| !e.trim().is_empty()).map(|e| entry_mode(e));
+    match modes.next() {
+        None => PagefileMode::None,
+        Some(first) if modes.all(|m| m == first) => first,
+        Some(_) => PagefileMode::Custom,
+    }
+}
+
+/// Check available physical memory in GB (a bare number like "8" is a minimum)
+pub fn check_available(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = check.expected_value.bare_as_minimum();
+    let expected_display = expected.describe(&|v| format!("{} GB", v));
+
+    match probe.memory_status() {
+        Ok(memory) => {
+            let available = memory.available_bytes as f64 / GB;
+            let current = format!("{:.1} GB of {:.1} GB", available, memory.total_bytes as f64 / GB);
+            if expected.matches(&format!("{:.1}", available)) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check commit charge as a percentage of the commit limit (a bare number
+/// like "80" is a maximum)
+pub fn check_commit_charge(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = check.expected_value.bare_as_maximum();
+    let expected_display = expected.describe(&|v| format!("{}%", v));
+
+    match probe.memory_status() {
+        Ok(memory) if memory.commit_limit_bytes > 0 => {
+            let percent = 100.0 * memory.commit_bytes as f64 / memory.commit_limit_bytes as f64;
+            let current = format!(
+                "{:.0}% ({:.1} GB of {:.1} GB)",
+                percent,
+                memory.commit_bytes as f64 / GB,
+                memory.commit_limit_bytes as f64 / GB
+            );
+            if expected.matches(&format!("{:.1}", percent)) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Ok(_) => CheckResult::error(&config.id, &config.name, "Commit limit is unknown"),
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check how the pagefile is sized (`system_managed`, `fixed`, `custom` or `none`)
+///
+/// The current value lists the configured pagefiles.
+pub fn check_pagefile(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = &check.expected_value;
+    let expected_display =
+        expected.describe(&|v| parse_pagefile_mode(v).map(|m| m.to_string()).unwrap_or_else(|| v.to_string()));
+
+    let entries = match registry::read_value(probe, MEMORY_MANAGEMENT_KEY, RegistryView::Native, "PagingFiles") {
+        Ok(RegistryValue::MultiString(entries)) => entries,
+        Ok(RegistryValue::String(entry)) => vec![entry],
+        Ok(other) => {
+            let message = format!("PagingFiles is stored as {}", other.value_type());
+            return CheckResult::error(&config.id, &config.name, &message);
+        }
+        Err(e) if e == KEY_NOT_FOUND || e == VALUE_NOT_FOUND => Vec::new(),
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    let mode = pagefile_mode(&entries);
+    let files: Vec<&str> = entries.iter().map(|e| e.trim()).filter(|e| !e.is_empty()).collect();
+    let current = if files.is_empty() {
+        mode.to_string()
+    } else {
+        format!("{} ({})", mode, files.join("; "))
+    };
+    let same = |e: &str, _: &str| parse_pagefile_mode(e) == Some(mode);
+
+    if expected.matches_by(mode.as_str(), &same) {
+        CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+    } else {
+        CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+    }
+}
@@ -0,0 +1,588 @@
+pub mod battery;
+pub mod disks;
+pub mod display;
+pub mod expectation;
+pub mod memory;
+pub mod power_plan;
+pub mod processes;
+pub mod registry;
+pub mod services;
+pub mod tasks;
+pub mod updates;
+
+use crate::config::{CheckConfig, CheckKind};
+use crate::probe::{Chassis, ProcessCacheProbe, RegistryValueType, SystemProbe};
+use serde::Serialize;
+use thiserror::Error;
+
+/// Result of a single check
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub id: String,
+    pub name: String,
+    pub passed: bool,
+    /// The check doesn't apply to this machine; neither passed nor failed
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+    pub current_value: String,
+    pub expected_value: String,
+    pub message: String,
+    /// Registry hive a registry check actually read (HKCU is redirected to
+    /// `HKU\<sid>` when running elevated as another account)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry_hive: Option<String>,
+}
+
+impl CheckResult {
+    pub fn pass(id: &str, name: &str, current: &str, expected: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            passed: true,
+            skipped: false,
+            current_value: current.to_string(),
+            expected_value: expected.to_string(),
+            message: format!("{} is correctly set", name),
+            registry_hive: None,
+        }
+    }
+
+    pub fn fail(id: &str, name: &str, current: &str, expected: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            passed: false,
+            skipped: false,
+            current_value: current.to_string(),
+            expected_value: expected.to_string(),
+            message: format!("{}: expected '{}', got '{}'", name, expected, current),
+            registry_hive: None,
+        }
+    }
+
+    /// The value exists but is stored with the wrong type
+    pub fn type_mismatch(id: &str, name: &str, actual_type: &str, expected_type: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            passed: false,
+            skipped: false,
+            current_value: actual_type.to_string(),
+            expected_value: expected_type.to_string(),
+            message: format!(
+                "{}: type mismatch, stored as {} instead of {}",
+                name, actual_type, expected_type
+            ),
+            registry_hive: None,
+        }
+    }
+
+    pub fn error(id: &str, name: &str, error: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            passed: false,
+            skipped: false,
+            current_value: "ERROR".to_string(),
+            expected_value: String::new(),
+            message: format!("{}: {}", name, error),
+            registry_hive: None,
+        }
+    }
+
+    /// The check doesn't apply here, e.g. a laptop-only check on a desktop
+    pub fn skip(id: &str, name: &str, reason: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            passed: false,
+            skipped: true,
+            current_value: "Skipped".to_string(),
+            expected_value: String::new(),
+            message: format!("{}: skipped, {}", name, reason),
+            registry_hive: None,
+        }
+    }
+
+    /// Failed or errored (skipped checks have not failed)
+    pub fn failed(&self) -> bool {
+        !self.passed && !self.skipped
+    }
+
+    /// Record the registry hive the check read
+    pub fn with_registry_hive(mut self, hive: String) -> Self {
+        self.registry_hive = Some(hive);
+        self
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum CheckError {
+    #[error("Windows API error: {0}")]
+    WindowsApi(String),
+
+    #[error("Registry error: {0}")]
+    Registry(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+}
+
+/// Run a check based on its configuration
+///
+/// Laptop-only checks are skipped on desktops; if the chassis can't be
+/// determined they run anyway.
+pub fn run_check(probe: &dyn SystemProbe, config: &CheckConfig) -> CheckResult {
+    if config.laptop_only && probe.chassis() == Ok(Chassis::Desktop) {
+        return CheckResult::skip(&config.id, &config.name, "laptop-only check on a desktop");
+    }
+
+    match &config.kind {
+        CheckKind::PowerScheme(check) => power_plan::check(probe, config, check),
+        CheckKind::PowerMode(check) => power_plan::check_power_mode(probe, config, check),
+        CheckKind::RegistryDword(check) => {
+            registry::check_value(probe, config, check, RegistryValueType::Dword)
+        }
+        CheckKind::RegistryQword(check) => {
+            registry::check_value(probe, config, check, RegistryValueType::Qword)
+        }
+        CheckKind::RegistryString(check) => {
+            registry::check_value(probe, config, check, RegistryValueType::String)
+        }
+        CheckKind::RegistryExpandString(check) => {
+            registry::check_value(probe, config, check, RegistryValueType::ExpandString)
+        }
+        CheckKind::RegistryMultiString(check) => {
+            registry::check_value(probe, config, check, RegistryValueType::MultiString)
+        }
+        CheckKind::RegistryBinary(check) => {
+            registry::check_value(probe, config, check, RegistryValueType::Binary)
+        }
+        CheckKind::RegistryExists(check) => registry::check_existence(probe, config, check, true),
+        CheckKind::RegistryAbsent(check) => registry::check_existence(probe, config, check, false),
+        CheckKind::ProcessAbsent(check) => processes::check_absent(probe, config, check),
+        CheckKind::ProcessPresent(check) => processes::check_present(probe, config, check),
+        CheckKind::DisplayResolution(check) => display::check_resolution(probe, config, check),
+        CheckKind::DisplayRefreshRate(check) => display::check_refresh_rate(probe, config, check),
+        CheckKind::DisplayCount(check) => display::check_display_count(probe, config, check),
+        CheckKind::DisplayBitDepth(check) => display::check_bit_depth(probe, config, check),
+        CheckKind::DisplayScaling(check) => display::check_scaling(probe, config, check),
+        CheckKind::DisplayOrientation(check) => display::check_orientation(probe, config, check),
+        CheckKind::HdrEnabled(check) => display::check_hdr(probe, config, check),
+        CheckKind::PowerSetting(check) => power_plan::check_setting(probe, config, check),
+        CheckKind::ServiceState(check) => services::check(probe, config, check),
+        CheckKind::TaskDisabled(check) => tasks::check_disabled(probe, config, check),
+        CheckKind::TaskNotRunning(check) => tasks::check_not_running(probe, config, check),
+        CheckKind::AcPower(check) => battery::check_ac_power(probe, config, check),
+        CheckKind::BatteryLevel(check) => battery::check_battery_level(probe, config, check),
+        CheckKind::CpuIdle(check) => processes::check_cpu_idle(probe, config, check),
+        CheckKind::MemoryAvailable(check) => memory::check_available(probe, config, check),
+        CheckKind::CommitCharge(check) => memory::check_commit_charge(probe, config, check),
+        CheckKind::Pagefile(check) => memory::check_pagefile(probe, config, check),
+        CheckKind::DiskFree(check) => disks::check_free(probe, config, check),
+        CheckKind::DriveType(check) => disks::check_drive_type(probe, config, check),
+        CheckKind::PendingReboot(check) => updates::check_pending_reboot(probe, config, check),
+        CheckKind::WindowsUpdateActive(check) => updates::check_update_active(probe, config, check),
+    }
+}
+
+/// Run all enabled checks and return results
+///
+/// Processes are listed once and shared by every process check in the pass.
+pub fn run_all_checks(probe: &dyn SystemProbe, checks: &[CheckConfig]) -> Vec<CheckResult> {
+    let probe = ProcessCacheProbe::new(probe);
+
+    checks
+        .iter()
+        .filter(|c| c.enabled)
+        .map(|c| run_check(&probe, c))
+        .collect()
+}
+
+/// Overall status derived from check results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverallStatus {
+    AllPassed,
+    SomeFailed,
+    AllFailed,
+}
+
+impl OverallStatus {
+    /// Skipped checks count neither way
+    pub fn from_results(results: &[CheckResult]) -> Self {
+        let passed = results.iter().filter(|r| r.passed).count();
+        let total = results.iter().filter(|r| !r.skipped).count();
+        if total == 0 {
+            return Self::AllPassed;
+        }
+
+        if passed == total {
+            Self::AllPassed
+        } else if passed == 0 {
+            Self::AllFailed
+        } else {
+            Self::SomeFailed
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probe::{DisplayOrientation, DriveKind, FakeProbe, RegistryHive, RegistryValue};
+    use serde_json::json;
+
+    fn check(value: serde_json::Value) -> CheckConfig {
+        serde_json::from_value(value).unwrap()
+    }
+
+    /// Run a check of `check_type` with the given fields on top of a stub id and name
+    fn run_json(probe: &dyn SystemProbe, check_type: &str, fields: serde_json::Value) -> CheckResult {
+        let mut value = json!({"id": "c", "name": "c", "check_type": check_type, "enabled": true});
+        value.as_object_mut().unwrap().extend(fields.as_object().unwrap().clone());
+        run_check(probe, &check(value))
+    }
+
+    #[test]
+    fn test_run_all_checks_against_fake() {
+        let probe = FakeProbe::new()
+            .with_dword(RegistryHive::CurrentUser, "Software\\Microsoft\\GameBar", "AutoGameModeEnabled", 1)
+            .with_process("C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe")
+            .with_display(2560, 1440, 165);
+
+        let checks = vec![
+            check(json!({
+                "id": "game_mode", "name": "game_mode", "check_type": "registry_dword", "enabled": true,
+                "registry_path": "HKCU\\Software\\Microsoft\\GameBar",
+                "registry_key": "AutoGameModeEnabled",
+                "expected_value": "1"
+            })),
+            check(json!({
+                "id": "no_chrome", "name": "no_chrome", "check_type": "process_absent", "enabled": true,
+                "process_name": "Chrome.exe"
+            })),
+            check(json!({
+                "id": "refresh", "name": "refresh", "check_type": "display_refresh_rate", "enabled": true,
+                "expected_value": "144"
+            })),
+            check(json!({
+                "id": "disabled", "name": "disabled", "check_type": "hdr_enabled", "enabled": false,
+                "expected_value": "1"
+            })),
+        ];
+
+        let results = run_all_checks(&probe, &checks);
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].passed);
+        assert!(!results[1].passed);
+        assert_eq!(
+            results[1].current_value,
+            "1 running: 1004 C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe"
+        );
+        assert!(results[2].passed);
+        assert_eq!(results[2].expected_value, ">= 144Hz");
+        assert_eq!(OverallStatus::from_results(&results), OverallStatus::SomeFailed);
+    }
+
+    #[test]
+    fn test_missing_registry_value_is_error() {
+        let probe = FakeProbe::new();
+        let config = check(json!({
+            "id": "hags", "name": "hags", "check_type": "registry_dword", "enabled": true,
+            "registry_path": "HKLM\\SYSTEM\\CurrentControlSet\\Control\\GraphicsDrivers",
+            "registry_key": "HwSchMode",
+            "expected_value": "2"
+        }));
+
+        let result = run_check(&probe, &config);
+
+        assert!(!result.passed);
+        assert_eq!(result.current_value, "ERROR");
+        assert_eq!(result.message, "hags: Key not found");
+    }
+
+    #[test]
+    fn test_registry_value_types() {
+        use crate::probe::RegistryValue;
+
+        let key = "Software\\Microsoft\\DirectX\\UserGpuPreferences";
+        let path = format!("HKCU\\{}", key);
+        let probe = FakeProbe::new()
+            .with_string(RegistryHive::CurrentUser, key, "DirectXUserGlobalSettings", "SwapEffectUpgradeEnable=1;")
+            .with_value(RegistryHive::CurrentUser, key, "Mask", RegistryValue::Qword(0x2000_0000_0000))
+            .with_value(RegistryHive::CurrentUser, key, "Apps", RegistryValue::MultiString(vec!["a.exe".into(), "b.exe".into()]))
+            .with_value(RegistryHive::CurrentUser, key, "Blob", RegistryValue::Binary(vec![0x01, 0x00, 0xff]));
+
+        let value = |name: &str, expected: &str| {
+            json!({"registry_path": path, "registry_key": name, "expected_value": expected})
+        };
+
+        assert!(run_json(&probe, "registry_qword", value("Mask", "0x200000000000")).passed);
+        assert!(run_json(&probe, "registry_multi_string", value("Apps", "a.exe; b.exe")).passed);
+        assert!(run_json(&probe, "registry_binary", value("Blob", "01 00 FF")).passed);
+        let upgrade = value("DirectXUserGlobalSettings", "regex: SwapEffectUpgradeEnable=1");
+        assert!(run_json(&probe, "registry_string", upgrade).passed);
+
+        let mismatch = run_json(&probe, "registry_dword", value("DirectXUserGlobalSettings", "1"));
+        assert!(!mismatch.passed);
+        assert_eq!(mismatch.current_value, "REG_SZ");
+        assert_eq!(mismatch.expected_value, "REG_DWORD");
+        assert!(mismatch.message.contains("type mismatch"));
+    }
+
+    #[test]
+    fn test_registry_view_and_hives() {
+        let probe = FakeProbe::new()
+            .with_dword(RegistryHive::LocalMachine, "SOFTWARE\\WOW6432Node\\Vendor\\Game", "FrameCap", 0)
+            .with_dword(RegistryHive::Users, "S-1-5-21-1000\\Software\\Vendor", "Overlay", 0);
+
+        let frame_cap = |view: Option<&str>| {
+            let mut fields = json!({
+                "registry_path": "HKLM\\SOFTWARE\\Vendor\\Game", "registry_key": "FrameCap", "expected_value": "0"
+            });
+            if let Some(view) = view {
+                fields["registry_view"] = json!(view);
+            }
+            run_json(&probe, "registry_dword", fields)
+        };
+
+        assert!(frame_cap(Some("32")).passed);
+        assert_eq!(frame_cap(None).message, "c: Key not found");
+        assert_eq!(frame_cap(Some("64")).message, "c: Key not found");
+
+        let user_check = check(json!({
+            "id": "overlay", "name": "overlay", "check_type": "registry_dword", "enabled": true,
+            "registry_path": "HKEY_USERS\\S-1-5-21-1000\\Software\\Vendor",
+            "registry_key": "Overlay",
+            "expected_value": "0"
+        }));
+        assert!(run_check(&probe, &user_check).passed);
+        assert!(user_check.validate().is_empty());
+
+        assert!(registry::validate_path("HKU\\Software\\Vendor").is_err());
+        assert!(registry::validate_path("HKU\\.DEFAULT\\Software").is_ok());
+        assert_eq!(
+            registry::parse_root_key("HKCR\\.exe"),
+            Some((RegistryHive::ClassesRoot, ".exe"))
+        );
+        assert!(registry::requires_admin("HKCC\\System"));
+    }
+
+    #[test]
+    fn test_process_patterns_and_counts() {
+        let probe = FakeProbe::new()
+            .with_process("C:\\Program Files (x86)\\Steam\\steam.exe")
+            .with_process("C:\\Program Files (x86)\\Steam\\bin\\cef\\cef.win7x64\\steamwebhelper.exe")
+            .with_process("C:\\Program Files (x86)\\Razer\\Synapse3\\Razer Synapse 3.exe")
+            .with_process("C:\\Program Files (x86)\\Razer\\Synapse3\\Razer Synapse Service.exe");
+
+        let razer = run_json(&probe, "process_absent", json!({"process_name": "razer*.exe"}));
+        assert!(!razer.passed);
+        assert!(razer.current_value.starts_with("2 running: 1012 C:\\"), "{}", razer.current_value);
+
+        let one_razer = run_json(&probe, "process_absent", json!({"process_name": "Razer*", "max_instances": 2}));
+        assert!(one_razer.passed);
+        assert_eq!(one_razer.expected_value, "at most 2 running");
+
+        let webhelper = run_json(
+            &probe,
+            "process_present",
+            json!({"process_name": "regex: ^steam", "process_path": "*\\bin\\cef\\*", "min_instances": 1}),
+        );
+        assert!(webhelper.passed);
+        assert!(webhelper.current_value.ends_with("steamwebhelper.exe"));
+
+        let too_few = run_json(&probe, "process_present", json!({"process_name": "steam*.exe", "min_instances": 3}));
+        assert!(!too_few.passed);
+    }
+
+    #[test]
+    fn test_display_targets_and_count() {
+        let probe = FakeProbe::new()
+            .with_display(3840, 2160, 144)
+            .with_monitor("\\\\.\\DISPLAY2", "DELL P2419H", 1920, 1080, 60);
+
+        let primary = run_json(&probe, "display_resolution", json!({"expected_value": "3840x2160"}));
+        assert!(primary.passed);
+
+        let by_device =
+            run_json(&probe, "display_refresh_rate", json!({"expected_value": "144", "display": "display2"}));
+        assert!(!by_device.passed);
+        assert_eq!(by_device.current_value, "60Hz");
+
+        let by_model =
+            run_json(&probe, "display_resolution", json!({"expected_value": "1920x1080", "display": "dell p2419h"}));
+        assert!(by_model.passed);
+
+        let missing =
+            run_json(&probe, "display_resolution", json!({"expected_value": "1920x1080", "display": "DISPLAY3"}));
+        assert!(!missing.passed);
+        assert!(missing.message.contains("No active display matches '\\\\.\\DISPLAY3'"), "{}", missing.message);
+
+        let count = run_json(&probe, "display_count", json!({"expected_value": "1"}));
+        assert!(!count.passed);
+        assert_eq!(
+            count.current_value,
+            "2 active: \\\\.\\DISPLAY1 Generic PnP Monitor; \\\\.\\DISPLAY2 DELL P2419H"
+        );
+    }
+
+    #[test]
+    fn test_display_bit_depth_scaling_and_orientation() {
+        let probe = FakeProbe::new()
+            .with_display(3840, 2160, 144)
+            .with_monitor("\\\\.\\DISPLAY2", "DELL P2419H", 1920, 1080, 60)
+            .with_display_settings("\\\\.\\DISPLAY2", 10, 125, DisplayOrientation::Portrait);
+
+        let deep_color = run_json(&probe, "display_bit_depth", json!({"expected_value": "10", "display": "DISPLAY2"}));
+        assert!(deep_color.passed);
+        assert_eq!(deep_color.expected_value, ">= 10-bit");
+        assert!(!run_json(&probe, "display_bit_depth", json!({"expected_value": "10"})).passed);
+
+        assert!(run_json(&probe, "display_scaling", json!({"expected_value": "100"})).passed);
+        let scaled = run_json(&probe, "display_scaling", json!({"expected_value": "100", "display": "DISPLAY2"}));
+        assert_eq!((scaled.passed, scaled.current_value.as_str()), (false, "125%"));
+
+        let rotated =
+            run_json(&probe, "display_orientation", json!({"expected_value": "Landscape", "display": "DISPLAY2"}));
+        assert!(!rotated.passed);
+        assert_eq!(rotated.current_value, "Portrait");
+        assert_eq!(rotated.expected_value, "Landscape");
+    }
+
+    #[test]
+    fn test_laptop_only_checks_skipped_on_desktop() {
+        let checks = vec![
+            check(json!({
+                "id": "plugged_in", "name": "Plugged In", "check_type": "ac_power", "enabled": true,
+                "laptop_only": true, "expected_value": "1"
+            })),
+            check(json!({
+                "id": "battery", "name": "Battery", "check_type": "battery_level", "enabled": true,
+                "expected_value": "80"
+            })),
+        ];
+
+        let desktop = run_all_checks(&FakeProbe::new(), &checks);
+        assert!(desktop.iter().all(|r| r.skipped && !r.failed()));
+        assert_eq!(OverallStatus::from_results(&desktop), OverallStatus::AllPassed);
+        let report = crate::report::CheckReport::new("s", "S", desktop);
+        assert_eq!((report.passed, report.total, report.skipped), (0, 0, 2));
+        assert!(report.to_text().contains("[SKIP] Plugged In: skipped, laptop-only check on a desktop"));
+
+        let laptop = run_all_checks(&FakeProbe::new().with_battery(false, 95), &checks);
+        assert!(!laptop[0].skipped && laptop[0].failed());
+        assert_eq!(laptop[0].current_value, "On battery");
+        assert_eq!((laptop[1].passed, laptop[1].current_value.as_str()), (true, "95%"));
+    }
+
+    #[test]
+    fn test_cpu_idle_lists_busiest_processes() {
+        let probe = FakeProbe::new()
+            .with_cpu_load("C:\\Windows\\System32\\svchost.exe", 0.5)
+            .with_cpu_load("C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe", 9.0)
+            .with_cpu_load("C:\\Program Files\\Windows Defender\\MsMpEng.exe", 3.2)
+            .with_cpu_load("C:\\Windows\\explorer.exe", 1.0);
+        let idle = check(json!({
+            "id": "idle", "name": "idle", "check_type": "cpu_idle", "enabled": true,
+            "expected_value": "5", "sample_seconds": 1
+        }));
+
+        let busy = run_check(&probe, &idle);
+        assert!(!busy.passed);
+        assert_eq!(busy.current_value, "13.7% busy (chrome.exe 9.0%, MsMpEng.exe 3.2%, explorer.exe 1.0%)");
+        assert_eq!(busy.expected_value, "<= 5% busy");
+
+        let chrome = probe.running_processes().unwrap().into_iter().find(|p| p.name == "chrome.exe").unwrap();
+        probe.terminate_process(chrome.pid).unwrap();
+        assert!(run_check(&probe, &idle).passed);
+
+        let starting = FakeProbe::new().with_cpu_sampled_for(2);
+        assert!(run_json(&starting, "cpu_idle", json!({"expected_value": "5", "sample_seconds": 1})).passed);
+        let unsampled = run_json(&starting, "cpu_idle", json!({"expected_value": "5", "sample_seconds": 3}));
+        assert!(unsampled.skipped);
+        assert_eq!(unsampled.message, "c: skipped, CPU load is still being sampled");
+    }
+
+    #[test]
+    fn test_memory_and_pagefile_checks() {
+        let memory_management = "SYSTEM\\CurrentControlSet\\Control\\Session Manager\\Memory Management";
+        let probe = FakeProbe::new().with_memory(6, 30, 36).with_value(
+            RegistryHive::LocalMachine,
+            memory_management,
+            "PagingFiles",
+            RegistryValue::MultiString(vec!["C:\\pagefile.sys 16384 16384".to_string()]),
+        );
+
+        let available = run_json(&probe, "memory_available", json!({"expected_value": "8"}));
+        assert_eq!((available.passed, available.current_value.as_str()), (false, "6.0 GB of 32.0 GB"));
+        let commit = run_json(&probe, "commit_charge", json!({"expected_value": "80"}));
+        assert_eq!((commit.passed, commit.current_value.as_str()), (false, "83% (30.0 GB of 36.0 GB)"));
+
+        let pagefile = run_json(&probe, "pagefile", json!({"expected_value": "fixed"}));
+        assert!(pagefile.passed);
+        assert_eq!(pagefile.current_value, "Fixed size (C:\\pagefile.sys 16384 16384)");
+        assert!(!run_json(&probe, "pagefile", json!({"expected_value": "system_managed"})).passed);
+
+        probe.set_value(
+            RegistryHive::LocalMachine,
+            memory_management,
+            "PagingFiles",
+            RegistryValue::MultiString(vec!["?:\\pagefile.sys".to_string()]),
+        );
+        assert!(run_json(&probe, "pagefile", json!({"expected_value": "system_managed"})).passed);
+    }
+
+    #[test]
+    fn test_disk_free_and_drive_type() {
+        let probe = FakeProbe::new()
+            .with_volume("D:", 80, 2000, DriveKind::Hdd)
+            .with_volume("E:", 150, 1000, DriveKind::Ssd);
+
+        let library = run_json(&probe, "disk_free", json!({"path": "d:\\Games\\Steam", "expected_value": "100"}));
+        assert!(!library.passed);
+        assert_eq!(library.current_value, "80.0 GB free of 2000.0 GB (4%) on D:\\");
+        assert_eq!(library.expected_value, ">= 100 GB free");
+        assert!(run_json(&probe, "disk_free", json!({"path": "E:", "expected_value": "10", "unit": "percent"})).passed);
+        assert!(!run_json(&probe, "disk_free", json!({"path": "F:", "expected_value": "10"})).passed);
+
+        assert!(run_json(&probe, "drive_type", json!({"path": "C:\\Games", "expected_value": "nvme"})).passed);
+        assert!(run_json(&probe, "drive_type", json!({"path": "C:", "expected_value": "ssd"})).passed);
+        assert!(run_json(&probe, "drive_type", json!({"path": "E:\\Captures", "expected_value": "ssd"})).passed);
+        let hdd = run_json(&probe, "drive_type", json!({"path": "D:\\Games", "expected_value": "one_of: [nvme, ssd]"}));
+        assert_eq!((hdd.passed, hdd.current_value.as_str()), (false, "HDD (D:\\Games)"));
+    }
+
+    #[test]
+    fn test_pending_reboot_and_windows_update() {
+        let session_manager = "SYSTEM\\CurrentControlSet\\Control\\Session Manager";
+        let idle = FakeProbe::new().with_value(
+            RegistryHive::LocalMachine,
+            session_manager,
+            "PendingFileRenameOperations",
+            RegistryValue::MultiString(vec![String::new()]),
+        );
+        let busy = FakeProbe::new()
+            .with_key(
+                RegistryHive::LocalMachine,
+                "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\WindowsUpdate\\Auto Update\\RebootRequired",
+            )
+            .with_value(
+                RegistryHive::LocalMachine,
+                session_manager,
+                "PendingFileRenameOperations",
+                RegistryValue::MultiString(vec!["\\??\\C:\\old.dll".to_string(), String::new()]),
+            )
+            .with_process("C:\\Windows\\WinSxS\\amd64_tiworker\\TiWorker.exe");
+
+        assert!(run_json(&idle, "pending_reboot", json!({"expected_value": "0"})).passed);
+        assert!(run_json(&idle, "windows_update_active", json!({"expected_value": "0"})).passed);
+        let reboot = run_json(&busy, "pending_reboot", json!({"expected_value": "0"}));
+        assert_eq!(reboot.current_value, "Reboot pending (Windows Update, pending file renames)");
+        assert_eq!(reboot.expected_value, "No reboot pending");
+        let update = run_json(&busy, "windows_update_active", json!({"expected_value": "0"}));
+        assert_eq!((update.passed, update.current_value.as_str()), (false, "Active: TiWorker.exe (installing)"));
+    }
+}
@@ -0,0 +1,475 @@
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, PowerSettingCheck, ValueCheck};
+use crate::probe::{Guid, PowerSource, SystemProbe};
+use serde::{Deserialize, Serialize};
+
+// Well-known power scheme GUIDs
+const GUID_HIGH_PERFORMANCE: Guid = Guid::from_u128(0x8c5e7fda_e8bf_4a96_9a85_a6e23a8c635c);
+const GUID_BALANCED: Guid = Guid::from_u128(0x381b4222_f694_41f0_9685_ff5bb260df2e);
+const GUID_POWER_SAVER: Guid = Guid::from_u128(0xa1841308_3541_4fab_bc81_f71556f20b4a);
+// Ultimate Performance (hidden on most systems until duplicated)
+const GUID_ULTIMATE_PERFORMANCE: Guid = Guid::from_u128(0xe9a42b02_d5df_448d_aa00_03f14749eb61);
+
+/// Built-in schemes: (GUID, GUID of the fixer's copy, key, display name)
+///
+/// Windows names a copied template in the display language, so the fixer
+/// gives its copy a fixed GUID and finds it again by that.
+type BuiltinScheme = (Guid, Guid, &'static str, &'static str);
+
+const BUILTIN_SCHEMES: &[BuiltinScheme] = &[
+    (
+        GUID_HIGH_PERFORMANCE,
+        Guid::from_u128(0x067f9548_abf7_4f24_b586_ad81ffd489bc),
+        "high_performance",
+        "High Performance",
+    ),
+    (GUID_BALANCED, Guid::from_u128(0xbbbda53d_1345_45d3_abb4_2008964abf00), "balanced", "Balanced"),
+    (GUID_POWER_SAVER, Guid::from_u128(0xe314c2c8_f82d_433b_bd75_8fe355e29aca), "power_saver", "Power Saver"),
+    (
+        GUID_ULTIMATE_PERFORMANCE,
+        Guid::from_u128(0x1fa7f533_7f36_4344_8f05_146a47f9b51c),
+        "ultimate_performance",
+        "Ultimate Performance",
+    ),
+];
+
+fn builtin_by_guid(guid: &Guid) -> Option<&'static BuiltinScheme> {
+    BUILTIN_SCHEMES.iter().find(|(g, copy, _, _)| g == guid || copy == guid)
+}
+
+fn builtin_by_key(key: &str) -> Option<&'static BuiltinScheme> {
+    BUILTIN_SCHEMES.iter().find(|(_, _, k, _)| *k == key)
+}
+
+/// Get human-readable name for a power scheme GUID
+fn scheme_name(guid: &Guid) -> &'static str {
+    builtin_by_guid(guid).map_or("Custom/Unknown", |(_, _, _, name)| name)
+}
+
+/// Get the scheme key for comparison
+fn scheme_key(guid: &Guid) -> &'static str {
+    builtin_by_guid(guid).map_or("custom", |(_, _, key, _)| key)
+}
+
+/// Parse expected value to check against
+fn parse_expected(expected: &str) -> Vec<&'static str> {
+    match expected.to_lowercase().as_str() {
+        "high_performance" | "high" => vec!["high_performance", "ultimate_performance"],
+        "ultimate_performance" | "ultimate" => vec!["ultimate_performance"],
+        "balanced" => vec!["balanced"],
+        "power_saver" | "saver" => vec!["power_saver"],
+        _ => vec!["custom"],
+    }
+}
+
+/// Whether a scheme satisfies one expected literal: a built-in key, a GUID
+/// or a plan's friendly name
+fn scheme_matches(expected: &str, guid: &Guid, installed_name: Option<&str>) -> bool {
+    let keys = parse_expected(expected);
+    if !keys.contains(&"custom") {
+        // Copies made outside the fixer get a random GUID, but on English
+        // systems they keep the template's name
+        return keys.contains(&scheme_key(guid))
+            || installed_name.is_some_and(|name| {
+                keys.iter()
+                    .filter_map(|key| builtin_by_key(key))
+                    .any(|(_, _, _, builtin)| builtin.eq_ignore_ascii_case(name))
+            });
+    }
+
+    match expected.parse::<Guid>() {
+        Ok(expected) => expected == *guid,
+        Err(_) => installed_name.is_some_and(|name| name.eq_ignore_ascii_case(expected.trim())),
+    }
+}
+
+/// Check the current power plan against expected
+pub fn check(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = &check.expected_value;
+
+    let current_guid = match probe.active_power_scheme() {
+        Ok(guid) => guid,
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    // Friendly name of the active plan (OEM plans, duplicated built-ins)
+    let installed = probe.power_schemes().unwrap_or_default();
+    let installed_name = installed
+        .iter()
+        .find(|s| s.guid == current_guid)
+        .map(|s| s.name.as_str())
+        .filter(|name| !name.is_empty());
+
+    let (current_key, current_name) = match builtin_by_guid(&current_guid) {
+        Some(_) => (scheme_key(&current_guid), scheme_name(&current_guid)),
+        None => {
+            let name = installed_name.unwrap_or("Custom/Unknown");
+            (name, name)
+        }
+    };
+
+    let acceptable = |expected: &str, _: &str| scheme_matches(expected, &current_guid, installed_name);
+
+    if expected.matches_by(current_key, &acceptable) {
+        CheckResult::pass(&config.id, &config.name, current_name, &expected.to_string())
+    } else {
+        CheckResult::fail(&config.id, &config.name, current_name, &expected.to_string())
+    }
+}
+
+/// Choices for the check editor: built-in keys, then installed plans by name
+pub fn scheme_choices(probe: &dyn SystemProbe) -> Vec<String> {
+    let mut choices: Vec<String> = BUILTIN_SCHEMES.iter().map(|(_, _, key, _)| key.to_string()).collect();
+
+    for scheme in probe.power_schemes().unwrap_or_default() {
+        let is_builtin = builtin_by_guid(&scheme.guid).is_some()
+            || BUILTIN_SCHEMES.iter().any(|(_, _, _, name)| name.eq_ignore_ascii_case(&scheme.name));
+        if !is_builtin && !scheme.name.is_empty() && !choices.contains(&scheme.name) {
+            choices.push(scheme.name);
+        }
+    }
+    choices
+}
+
+/// Activate the power plan named by a key, GUID or friendly name
+///
+/// A built-in plan that isn't installed (Ultimate Performance is hidden on
+/// most systems) is added by duplicating its template first, under the
+/// copy GUID so later passes find it in any display language.
+/// Returns a description of what was done.
+pub fn set_power_scheme(probe: &dyn SystemProbe, target: &str) -> Result<String, String> {
+    let installed = probe.power_schemes()?;
+
+    if let Some(&(guid, copy, _, name)) = parse_expected(target).first().and_then(|key| builtin_by_key(key)) {
+        let existing = installed
+            .iter()
+            .find(|s| s.guid == guid || s.guid == copy)
+            .or_else(|| installed.iter().find(|s| s.name.eq_ignore_ascii_case(name)));
+
+        return match existing {
+            Some(scheme) => {
+                probe.set_active_power_scheme(scheme.guid)?;
+                Ok(format!("Set power plan to {}", name))
+            }
+            None => {
+                probe.duplicate_power_scheme(guid, copy)?;
+                probe.set_active_power_scheme(copy)?;
+                Ok(format!("Added the hidden {} plan and set it active", name))
+            }
+        };
+    }
+
+    let scheme = match target.parse::<Guid>() {
+        Ok(guid) => installed.iter().find(|s| s.guid == guid),
+        Err(_) => installed.iter().find(|s| s.name.eq_ignore_ascii_case(target.trim())),
+    }
+    .ok_or_else(|| format!("No installed power plan matches '{}'", target))?;
+
+    probe.set_active_power_scheme(scheme.guid)?;
+    Ok(format!("Set power plan to {}", scheme.name))
+}
+
+// ===== Power Mode (Overlay Scheme) Support =====
+// Power Mode is the slider in Windows 10/11 Settings > Power & battery
+// It's separate from Power Plans and controls performance overlay
+
+// Power mode overlay GUIDs (Windows 10 1709+)
+const GUID_POWER_MODE_BETTER_BATTERY: Guid = Guid::from_u128(0x961cc777_2547_4f9d_8174_7d86181b8a7a);
+const GUID_POWER_MODE_BALANCED: Guid = Guid::from_u128(0x00000000_0000_0000_0000_000000000000); // All zeros = balanced/default
+const GUID_POWER_MODE_BETTER_PERFORMANCE: Guid = Guid::from_u128(0x3af9b8d9_7c97_431d_ad78_34a8bfea439f);
+const GUID_POWER_MODE_BEST_PERFORMANCE: Guid = Guid::from_u128(0xded574b5_45a0_4f42_8737_46345c09c238);
+
+/// Get human-readable name for a power mode GUID
+fn power_mode_name(guid: &Guid) -> &'static str {
+    if *guid == GUID_POWER_MODE_BEST_PERFORMANCE {
+        "Best Performance"
+    } else if *guid == GUID_POWER_MODE_BETTER_PERFORMANCE {
+        "Better Performance"
+    } else if *guid == GUID_POWER_MODE_BALANCED || guid.to_u128() == 0 {
+        "Balanced"
+    } else if *guid == GUID_POWER_MODE_BETTER_BATTERY {
+        "Better Battery"
+    } else {
+        "Unknown"
+    }
+}
+
+/// Get the power mode key for comparison
+fn power_mode_key(guid: &Guid) -> &'static str {
+    if *guid == GUID_POWER_MODE_BEST_PERFORMANCE {
+        "best_performance"
+    } else if *guid == GUID_POWER_MODE_BETTER_PERFORMANCE {
+        "better_performance"
+    } else if *guid == GUID_POWER_MODE_BALANCED || guid.to_u128() == 0 {
+        "balanced"
+    } else if *guid == GUID_POWER_MODE_BETTER_BATTERY {
+        "better_battery"
+    } else {
+        "unknown"
+    }
+}
+
+/// Parse expected power mode value
+fn parse_expected_mode(expected: &str) -> Vec<&'static str> {
+    match expected.to_lowercase().as_str() {
+        "best_performance" | "best" | "max" => vec!["best_performance"],
+        "better_performance" | "better" | "high" => vec!["better_performance", "best_performance"],
+        "balanced" | "default" => vec!["balanced"],
+        "better_battery" | "battery" | "saver" => vec!["better_battery"],
+        _ => vec!["unknown"],
+    }
+}
+
+/// Whether an expected value names a known power mode
+pub fn is_known_mode(expected: &str) -> bool {
+    !parse_expected_mode(expected).contains(&"unknown")
+}
+
+/// Check the current power mode (overlay scheme) against expected
+pub fn check_power_mode(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = &check.expected_value;
+
+    let mode_guid = match probe.power_mode() {
+        Ok(guid) => guid,
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    let current_key = power_mode_key(&mode_guid);
+    let current_name = power_mode_name(&mode_guid);
+
+    let acceptable =
+        |expected: &str, current: &str| parse_expected_mode(expected).contains(&current);
+
+    if expected.matches_by(current_key, &acceptable) {
+        CheckResult::pass(&config.id, &config.name, current_name, &expected.to_string())
+    } else {
+        CheckResult::fail(&config.id, &config.name, current_name, &expected.to_string())
+    }
+}
+
+/// Set the active power mode (overlay scheme) by key name
+/// Returns Ok(()) on success, Err with message on failure
+pub fn set_power_mode(probe: &dyn SystemProbe, mode_key: &str) -> Result<(), String> {
+    let guid = match mode_key.to_lowercase().as_str() {
+        "best_performance" | "best" | "max" => GUID_POWER_MODE_BEST_PERFORMANCE,
+        "better_performance" | "better" | "high" => GUID_POWER_MODE_BETTER_PERFORMANCE,
+        "balanced" | "default" => GUID_POWER_MODE_BALANCED,
+        "better_battery" | "battery" | "saver" => GUID_POWER_MODE_BETTER_BATTERY,
+        _ => return Err(format!("Unknown power mode: {}", mode_key)),
+    };
+
+    probe.set_power_mode(guid)
+}
+
+// ===== Power Settings (values inside a scheme) =====
+
+// Subgroup GUIDs
+const GUID_SUB_PROCESSOR: Guid = Guid::from_u128(0x54533251_82be_4824_96c1_47b60b740d00);
+const GUID_SUB_PCIEXPRESS: Guid = Guid::from_u128(0x501a4d13_42af_4429_9fd1_a8218c268e20);
+const GUID_SUB_USB: Guid = Guid::from_u128(0x2a737441_1930_4402_8d77_b2bebba308a3);
+
+/// Friendly names for common settings: (alias, subgroup, setting)
+const POWER_SETTING_ALIASES: &[(&str, Guid, Guid)] = &[
+    // 0 = disabled, 1 = enabled, 2 = aggressive, ...
+    ("processor_boost_mode", GUID_SUB_PROCESSOR, Guid::from_u128(0xbe337238_0d82_4146_a960_4f3749d470c7)),
+    // Percent
+    ("min_processor_state", GUID_SUB_PROCESSOR, Guid::from_u128(0x893dee8e_2bef_41e0_89c6_b55d0929964c)),
+    ("max_processor_state", GUID_SUB_PROCESSOR, Guid::from_u128(0xbc5038f7_23e0_4960_96da_33abaf5935ec)),
+    // Percent of cores kept unparked
+    ("core_parking_min_cores", GUID_SUB_PROCESSOR, Guid::from_u128(0x0cc5b647_c1df_4637_891a_dec35c318583)),
+    ("core_parking_max_cores", GUID_SUB_PROCESSOR, Guid::from_u128(0xea062031_0e34_4ff1_9b6d_eb1059334028)),
+    // 0 = off, 1 = moderate, 2 = maximum savings
+    ("pcie_link_state", GUID_SUB_PCIEXPRESS, Guid::from_u128(0xee12f906_d277_404b_b6da_e5fa1a576df5)),
+    // 0 = disabled, 1 = enabled
+    ("usb_selective_suspend", GUID_SUB_USB, Guid::from_u128(0x48e6b7a6_50f5_4782_a5d4_53bb8f07e226)),
+];
+
+/// A setting inside a power scheme (stored in the config as text)
+///
+/// - `processor_boost_mode`: a friendly alias
+/// - `54533251-.../be337238-...`: subgroup GUID / setting GUID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PowerSetting {
+    text: String,
+    pub subgroup: Guid,
+    pub setting: Guid,
+}
+
+impl PowerSetting {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let text = s.trim();
+
+        let (subgroup, setting) = match POWER_SETTING_ALIASES
+            .iter()
+            .find(|(alias, _, _)| alias.eq_ignore_ascii_case(text))
+        {
+            Some(&(_, subgroup, setting)) => (subgroup, setting),
+            None => {
+                let (subgroup, setting) = text.split_once('/').ok_or_else(|| {
+                    format!("unknown power setting '{}' (use an alias or SUBGROUP_GUID/SETTING_GUID)", text)
+                })?;
+                (subgroup.parse()?, setting.parse()?)
+            }
+        };
+
+        Ok(Self {
+            text: text.to_string(),
+            subgroup,
+            setting,
+        })
+    }
+
+    /// The setting as written in the config
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+impl TryFrom<String> for PowerSetting {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        Self::parse(&s)
+    }
+}
+
+impl From<PowerSetting> for String {
+    fn from(setting: PowerSetting) -> String {
+        setting.text
+    }
+}
+
+/// Power sources a check covers: the one configured, or both
+pub fn power_sources(check: &PowerSettingCheck) -> Vec<PowerSource> {
+    match check.power_source {
+        Some(source) => vec![source],
+        None => vec![PowerSource::Ac, PowerSource::Dc],
+    }
+}
+
+/// Check a setting's AC and/or DC value in the active power scheme
+pub fn check_setting(probe: &dyn SystemProbe, config: &CheckConfig, check: &PowerSettingCheck) -> CheckResult {
+    let expected = check.expected_value.to_string();
+
+    let values = match probe
+        .active_power_scheme()
+        .and_then(|scheme| probe.power_setting(scheme, check.power_setting.subgroup, check.power_setting.setting))
+    {
+        Ok(values) => values,
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    let sources = power_sources(check);
+    let current = match sources.as_slice() {
+        [source] => values.get(*source).to_string(),
+        _ => sources
+            .iter()
+            .map(|s| format!("{} {}", s, values.get(*s)))
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+
+    if sources
+        .iter()
+        .all(|s| check.expected_value.matches(&values.get(*s).to_string()))
+    {
+        CheckResult::pass(&config.id, &config.name, &current, &expected)
+    } else {
+        CheckResult::fail(&config.id, &config.name, &current, &expected)
+    }
+}
+
+/// Write a value to the checked sources of a setting in the active scheme
+pub fn set_power_setting(probe: &dyn SystemProbe, check: &PowerSettingCheck, value: u32) -> Result<(), String> {
+    let scheme = probe.active_power_scheme()?;
+    let setting = &check.power_setting;
+
+    for source in power_sources(check) {
+        probe.set_power_setting(scheme, setting.subgroup, setting.setting, source, value)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::expectation::Expectation;
+    use crate::config::CheckKind;
+    use crate::probe::FakeProbe;
+
+    #[test]
+    fn test_scheme_name() {
+        assert_eq!(scheme_name(&GUID_HIGH_PERFORMANCE), "High Performance");
+        assert_eq!(scheme_name(&GUID_BALANCED), "Balanced");
+    }
+
+    #[test]
+    fn test_power_setting_alias_and_guids() {
+        let alias = PowerSetting::parse("Processor_Boost_Mode").unwrap();
+        let guids =
+            PowerSetting::parse("54533251-82be-4824-96c1-47b60b740d00/be337238-0d82-4146-a960-4f3749d470c7").unwrap();
+        assert_eq!((alias.subgroup, alias.setting), (guids.subgroup, guids.setting));
+        assert!(PowerSetting::parse("turbo").is_err());
+    }
+
+    #[test]
+    fn test_oem_plan_and_hidden_ultimate() {
+        let oem = Guid::from_u128(0x64a64f24_65b9_4b56_befd_5ec1eaced9b3);
+        let probe = FakeProbe::new()
+            .with_installed_scheme(oem, "ASUS Turbo")
+            .with_power_scheme(oem);
+
+        let plan_check = |expected: &str| {
+            let value = ValueCheck {
+                expected_value: Expectation::parse(expected).unwrap(),
+            };
+            let config = CheckConfig::new("plan", "Power Plan", CheckKind::PowerScheme(value.clone()));
+            check(&probe, &config, &value)
+        };
+
+        let result = plan_check("asus turbo");
+        assert!(result.passed);
+        assert_eq!(result.current_value, "ASUS Turbo");
+        assert!(plan_check("64a64f24-65b9-4b56-befd-5ec1eaced9b3").passed);
+        assert!(!plan_check("ultimate_performance").passed);
+
+        assert_eq!(
+            set_power_scheme(&probe, "ultimate_performance").unwrap(),
+            "Added the hidden Ultimate Performance plan and set it active"
+        );
+        assert!(plan_check("ultimate_performance").passed);
+        assert!(plan_check("high_performance").passed);
+
+        // The copy is reused rather than duplicated again
+        set_power_scheme(&probe, "ASUS Turbo").unwrap();
+        assert_eq!(
+            set_power_scheme(&probe, "ultimate").unwrap(),
+            "Set power plan to Ultimate Performance"
+        );
+        assert_eq!(probe.power_schemes().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_localized_ultimate_copy_found_by_guid() {
+        let (_, copy, _, _) = *builtin_by_key("ultimate_performance").unwrap();
+        let probe = FakeProbe::new()
+            .with_installed_scheme(copy, "Ultimative Leistung")
+            .with_power_scheme(copy);
+
+        let value = ValueCheck {
+            expected_value: Expectation::parse("ultimate_performance").unwrap(),
+        };
+        let config = CheckConfig::new("plan", "Power Plan", CheckKind::PowerScheme(value.clone()));
+        let result = check(&probe, &config, &value);
+        assert!(result.passed);
+        assert_eq!(result.current_value, "Ultimate Performance");
+
+        let before = probe.power_schemes().unwrap().len();
+        assert_eq!(
+            set_power_scheme(&probe, "ultimate").unwrap(),
+            "Set power plan to Ultimate Performance"
+        );
+        assert_eq!(probe.power_schemes().unwrap().len(), before);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, CpuIdleCheck, ProcessCheck};
+use crate::probe::{ProcessInfo, SystemProbe, CPU_NOT_SAMPLED};
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Shortest window to measure over when a CPU idle check doesn't set one
+const DEFAULT_SAMPLE_SECONDS: u32 = 3;
+
+/// How many of the busiest processes a CPU idle result lists
+const TOP_PROCESSES: usize = 3;
+
+/// Pattern for a process name or image path (stored in the config as text)
+///
+/// - `chrome.exe`: exact match, case-insensitive
+/// - `Razer*.exe`: glob, `*` and `?` wildcards
+/// - `regex: ^steam(webhelper)?\.exe$`: regular expression
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ProcessPattern {
+    text: String,
+    regex: Regex,
+}
+
+impl ProcessPattern {
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let text = s.trim();
+        let source = match text.strip_prefix("regex:") {
+            Some(rest) => rest.trim().to_string(),
+            None => glob_to_regex(text),
+        };
+
+        let regex = RegexBuilder::new(&source)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| format!("invalid process pattern '{}': {}", text, e))?;
+
+        Ok(Self {
+            text: text.to_string(),
+            regex,
+        })
+    }
+
+    /// The pattern as written in the config
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn is_match(&self, s: &str) -> bool {
+        self.regex.is_match(s)
+    }
+}
+
+impl TryFrom<String> for ProcessPattern {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        Self::parse(&s)
+    }
+}
+
+impl From<ProcessPattern> for String {
+    fn from(pattern: ProcessPattern) -> String {
+        pattern.text
+    }
+}
+
+/// Anchored regex for a glob; without wildcards this is an exact match
+fn glob_to_regex(glob: &str) -> String {
+    let mut source = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => source.push_str(".*"),
+            '?' => source.push('.'),
+            c => source.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    source.push('$');
+    source
+}
+
+/// Find running processes matching a check's name and path patterns, with
+/// their image paths (empty if they can't be queried)
+///
+/// Paths are only looked up for processes whose name matches.
+fn find_processes(probe: &dyn SystemProbe, check: &ProcessCheck) -> Result<Vec<(ProcessInfo, String)>, String> {
+    Ok(probe
+        .running_processes()?
+        .into_iter()
+        .filter(|p| check.process_name.is_match(&p.name))
+        .map(|p| {
+            let path = probe.process_path(p.pid).unwrap_or_default();
+            (p, path)
+        })
+        .filter(|(_, path)| check.process_path.as_ref().is_none_or(|pattern| pattern.is_match(path)))
+        .collect())
+}
+
+/// Allowed instance counts: explicit bounds, or the check type's default
+fn instance_bounds(check: &ProcessCheck, should_run: bool) -> (u32, Option<u32>) {
+    if should_run {
+        (check.min_instances.unwrap_or(1), check.max_instances)
+    } else {
+        (check.min_instances.unwrap_or(0), Some(check.max_instances.unwrap_or(0)))
+    }
+}
+
+/// Describe allowed instance counts ("Not Running", "Running", "at most 2 running")
+fn describe_bounds(min: u32, max: Option<u32>) -> String {
+    match (min, max) {
+        (0, Some(0)) => "Not Running".to_string(),
+        (1, None) => "Running".to_string(),
+        (min, None) => format!("at least {} running", min),
+        (0, Some(max)) => format!("at most {} running", max),
+        (min, Some(max)) if min == max => format!("exactly {} running", min),
+        (min, Some(max)) => format!("{}..={} running", min, max),
+    }
+}
+
+/// List matched processes as "<count> running: <pid> <path>; ..."
+fn describe_matches(processes: &[(ProcessInfo, String)]) -> String {
+    if processes.is_empty() {
+        return "Not Running".to_string();
+    }
+
+    let list: Vec<String> = processes
+        .iter()
+        .map(|(p, path)| {
+            let image = if path.is_empty() { &p.name } else { path };
+            format!("{} {}", p.pid, image)
+        })
+        .collect();
+    format!("{} running: {}", processes.len(), list.join("; "))
+}
+
+/// Check the number of matching processes against the allowed bounds
+fn check_instances(
+    probe: &dyn SystemProbe,
+    config: &CheckConfig,
+    check: &ProcessCheck,
+    should_run: bool,
+) -> CheckResult {
+    let processes = match find_processes(probe, check) {
+        Ok(processes) => processes,
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    let (min, max) = instance_bounds(check, should_run);
+    let count = processes.len() as u32;
+    let current = describe_matches(&processes);
+    let expected = describe_bounds(min, max);
+
+    if count >= min && max.is_none_or(|max| count <= max) {
+        CheckResult::pass(&config.id, &config.name, &current, &expected)
+    } else {
+        CheckResult::fail(&config.id, &config.name, &current, &expected)
+    }
+}
+
+/// Check that a process is NOT running (or runs at most `max_instances` times)
+pub fn check_absent(probe: &dyn SystemProbe, config: &CheckConfig, check: &ProcessCheck) -> CheckResult {
+    check_instances(probe, config, check, false)
+}
+
+/// Check that a process IS running (at least `min_instances` times)
+pub fn check_present(probe: &dyn SystemProbe, config: &CheckConfig, check: &ProcessCheck) -> CheckResult {
+    check_instances(probe, config, check, true)
+}
+
+/// Terminate matching processes beyond the check's allowed maximum
+/// Returns Ok(count) with number of processes terminated, or Err on failure
+pub fn terminate_process(probe: &dyn SystemProbe, check: &ProcessCheck) -> Result<u32, String> {
+    let keep = check.max_instances.unwrap_or(0) as usize;
+    let mut terminated_count = 0u32;
+
+    for (process, _) in find_processes(probe, check)?.iter().skip(keep) {
+        if probe.terminate_process(process.pid).is_ok() {
+            terminated_count += 1;
+        }
+    }
+
+    Ok(terminated_count)
+}
+
+/// Check that the system is idle: CPU utilization over the sample window
+/// stays under the expected percentage (a bare number like "5" is a maximum)
+///
+/// The current value lists the busiest processes, e.g.
+/// "12.4% busy (chrome.exe 6.1%, MsMpEng.exe 3.2%)". Until the probe has
+/// sampled for a full window the check is skipped.
+pub fn check_cpu_idle(probe: &dyn SystemProbe, config: &CheckConfig, check: &CpuIdleCheck) -> CheckResult {
+    let expected = check.expected_value.bare_as_maximum();
+    let expected_display = expected.describe(&|v| format!("{}% busy", v));
+    let window = Duration::from_secs(check.sample_seconds.unwrap_or(DEFAULT_SAMPLE_SECONDS).into());
+
+    match probe.cpu_usage(window) {
+        Ok(usage) => {
+            let top: Vec<String> = usage
+                .processes
+                .iter()
+                .filter(|p| p.percent >= 0.1)
+                .take(TOP_PROCESSES)
+                .map(|p| format!("{} {:.1}%", p.name, p.percent))
+                .collect();
+            let mut current = format!("{:.1}% busy", usage.busy_percent);
+            if !top.is_empty() {
+                current.push_str(&format!(" ({})", top.join(", ")));
+            }
+
+            if expected.matches(&format!("{:.1}", usage.busy_percent)) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+            }
+        }
+        Err(e) if e == CPU_NOT_SAMPLED => {
+            CheckResult::skip(&config.id, &config.name, "CPU load is still being sampled")
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
@@ -0,0 +1,288 @@
+use crate::checkers::expectation::Expectation;
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, RegistryCheck, RegistryExistenceCheck};
+use crate::probe::{
+    RegistryHive, RegistryValue, RegistryValueType, RegistryView, SystemProbe, KEY_NOT_FOUND,
+    VALUE_NOT_FOUND,
+};
+
+/// Root key prefixes, short and long form
+const ROOT_KEYS: [(&str, &str, RegistryHive); 5] = [
+    ("HKCU", "HKEY_CURRENT_USER", RegistryHive::CurrentUser),
+    ("HKLM", "HKEY_LOCAL_MACHINE", RegistryHive::LocalMachine),
+    ("HKU", "HKEY_USERS", RegistryHive::Users),
+    ("HKCR", "HKEY_CLASSES_ROOT", RegistryHive::ClassesRoot),
+    ("HKCC", "HKEY_CURRENT_CONFIG", RegistryHive::CurrentConfig),
+];
+
+/// Parse the root key from a registry path
+pub fn parse_root_key(path: &str) -> Option<(RegistryHive, &str)> {
+    let (root, subpath) = path.split_once('\\')?;
+    ROOT_KEYS
+        .iter()
+        .find(|(short, long, _)| root.eq_ignore_ascii_case(short) || root.eq_ignore_ascii_case(long))
+        .map(|(_, _, hive)| (*hive, subpath))
+}
+
+/// Check that a path names a known root key, and a user for HKU paths
+pub fn validate_path(path: &str) -> Result<(), String> {
+    let (hive, subpath) =
+        parse_root_key(path).ok_or_else(|| format!("invalid registry path '{}'", path))?;
+
+    if hive == RegistryHive::Users {
+        let user = subpath.split('\\').next().unwrap_or_default();
+        if !is_user_key(user) {
+            return Err(format!(
+                "HKU path '{}' must start with a user SID (e.g. HKU\\S-1-5-21-...\\Software) or .DEFAULT",
+                path
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether a subkey of HKEY_USERS names a user profile (`S-1-...` or `.DEFAULT`)
+fn is_user_key(name: &str) -> bool {
+    if name.eq_ignore_ascii_case(".DEFAULT") {
+        return true;
+    }
+    let name = name.strip_suffix("_Classes").unwrap_or(name);
+    let mut parts = name.split('-');
+    parts.next().is_some_and(|p| p.eq_ignore_ascii_case("S"))
+        && parts.clone().count() >= 2
+        && parts.all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Hive and subkey a registry path is actually read from or written to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPath {
+    pub hive: RegistryHive,
+    pub subkey: String,
+}
+
+impl ResolvedPath {
+    /// Root key as written in paths, with the user for HKU (e.g. `HKU\S-1-5-21-...`)
+    pub fn hive_label(&self) -> String {
+        let root = ROOT_KEYS
+            .iter()
+            .find(|(_, _, hive)| *hive == self.hive)
+            .map(|(short, _, _)| *short)
+            .unwrap_or_default();
+
+        match self.hive {
+            RegistryHive::Users => {
+                format!("{}\\{}", root, self.subkey.split('\\').next().unwrap_or_default())
+            }
+            _ => root.to_string(),
+        }
+    }
+}
+
+/// Resolve a registry path for the probe
+///
+/// When the probe runs elevated as another account, HKCU is that account's
+/// hive rather than the logged-on user's, so HKCU paths go to `HKU\<sid>`.
+pub fn resolve_path(probe: &dyn SystemProbe, path: &str) -> Result<ResolvedPath, String> {
+    let (hive, subkey) =
+        parse_root_key(path).ok_or_else(|| format!("Invalid registry path: {}", path))?;
+
+    match (hive, probe.interactive_user_sid()) {
+        (RegistryHive::CurrentUser, Some(sid)) => Ok(ResolvedPath {
+            hive: RegistryHive::Users,
+            subkey: format!("{}\\{}", sid, subkey),
+        }),
+        _ => Ok(ResolvedPath {
+            hive,
+            subkey: subkey.to_string(),
+        }),
+    }
+}
+
+/// Check if writing under a registry path requires admin privileges
+/// (anything outside the current user's own hive)
+pub fn requires_admin(path: &str) -> bool {
+    parse_root_key(path).is_some_and(|(hive, _)| hive != RegistryHive::CurrentUser)
+}
+
+/// Check a registry value that should be stored as `value_type`
+///
+/// A value stored with a different type fails as a type mismatch.
+pub fn check_value(
+    probe: &dyn SystemProbe,
+    config: &CheckConfig,
+    check: &RegistryCheck,
+    value_type: RegistryValueType,
+) -> CheckResult {
+    let expected = &check.expected_value;
+
+    let path = match resolve_path(probe, &check.registry_path) {
+        Ok(path) => path,
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    let result = match probe.read_value(path.hive, check.registry_view, &path.subkey, &check.registry_key) {
+        Ok(value) if value.value_type() != value_type => CheckResult::type_mismatch(
+            &config.id,
+            &config.name,
+            &value.value_type().to_string(),
+            &value_type.to_string(),
+        ),
+        Ok(value) => {
+            let current = value_text(&value);
+            if value_matches(&value, expected) {
+                CheckResult::pass(&config.id, &config.name, &current, &expected.to_string())
+            } else {
+                CheckResult::fail(&config.id, &config.name, &current, &expected.to_string())
+            }
+        }
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    };
+    result.with_registry_hive(path.hive_label())
+}
+
+/// Check that a registry key (or a value in it) exists, or that it doesn't
+pub fn check_existence(
+    probe: &dyn SystemProbe,
+    config: &CheckConfig,
+    check: &RegistryExistenceCheck,
+    should_exist: bool,
+) -> CheckResult {
+    let path = match resolve_path(probe, &check.registry_path) {
+        Ok(path) => path,
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    // Without a value name, read the key's default value: "Value not found"
+    // then still means the key exists
+    let value_name = check.registry_key.as_deref().unwrap_or("");
+    let exists = match probe.read_value(path.hive, check.registry_view, &path.subkey, value_name) {
+        Ok(_) => true,
+        Err(e) if e == VALUE_NOT_FOUND => check.registry_key.is_none(),
+        Err(e) if e == KEY_NOT_FOUND => false,
+        Err(e) => {
+            return CheckResult::error(&config.id, &config.name, &e).with_registry_hive(path.hive_label())
+        }
+    };
+
+    let state = |present: bool| if present { "Present" } else { "Absent" };
+    let result = if exists == should_exist {
+        CheckResult::pass(&config.id, &config.name, state(exists), state(should_exist))
+    } else {
+        CheckResult::fail(&config.id, &config.name, state(exists), state(should_exist))
+    };
+    result.with_registry_hive(path.hive_label())
+}
+
+/// Delete a value from the registry using full path
+pub fn delete_value(
+    probe: &dyn SystemProbe,
+    path: &str,
+    view: RegistryView,
+    value_name: &str,
+) -> Result<(), String> {
+    let path = resolve_path(probe, path)?;
+    probe.delete_value(path.hive, view, &path.subkey, value_name)
+}
+
+/// Render a value as text (what `expected_value` literals are written as)
+///
+/// Multi-strings are `;`-separated and binary data is space-separated hex bytes.
+pub fn value_text(value: &RegistryValue) -> String {
+    match value {
+        RegistryValue::Dword(v) => v.to_string(),
+        RegistryValue::Qword(v) => v.to_string(),
+        RegistryValue::String(s) | RegistryValue::ExpandString(s) => s.clone(),
+        RegistryValue::MultiString(entries) => entries.join("; "),
+        RegistryValue::Binary(bytes) => bytes
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Parse an expected_value literal as data of the given type (also used by the fixer)
+pub fn parse_value(value_type: RegistryValueType, text: &str) -> Result<RegistryValue, String> {
+    let text = text.trim();
+    match value_type {
+        RegistryValueType::Dword => parse_integer(text)
+            .and_then(|v| u32::try_from(v).ok())
+            .map(RegistryValue::Dword)
+            .ok_or_else(|| format!("'{}' is not a DWORD", text)),
+        RegistryValueType::Qword => parse_integer(text)
+            .map(RegistryValue::Qword)
+            .ok_or_else(|| format!("'{}' is not a QWORD", text)),
+        RegistryValueType::String => Ok(RegistryValue::String(text.to_string())),
+        RegistryValueType::ExpandString => Ok(RegistryValue::ExpandString(text.to_string())),
+        RegistryValueType::MultiString => Ok(RegistryValue::MultiString(
+            text.split(';')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        )),
+        RegistryValueType::Binary => parse_hex_bytes(text)
+            .map(RegistryValue::Binary)
+            .ok_or_else(|| format!("'{}' is not hex bytes (e.g. \"01 00 ff\")", text)),
+    }
+}
+
+/// Decimal or `0x`-prefixed hex integer
+fn parse_integer(text: &str) -> Option<u64> {
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+/// Hex bytes, optionally separated by spaces, commas or dashes
+fn parse_hex_bytes(text: &str) -> Option<Vec<u8>> {
+    let hex: String = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',' && *c != '-')
+        .collect();
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Compare a typed value against an expectation
+/// Literals are parsed as the value's type; ordering and regex use the value's text
+fn value_matches(value: &RegistryValue, expected: &Expectation) -> bool {
+    let same_data = |literal: &str, _: &str| {
+        parse_value(value.value_type(), literal).is_ok_and(|v| v == *value)
+    };
+
+    match value {
+        // Multi-strings: regex sees one entry per line
+        RegistryValue::MultiString(entries) => expected.matches_by(&entries.join("\n"), &same_data),
+        _ => expected.matches_by(&value_text(value), &same_data),
+    }
+}
+
+/// Read a value from the registry using full path
+pub fn read_value(
+    probe: &dyn SystemProbe,
+    path: &str,
+    view: RegistryView,
+    value_name: &str,
+) -> Result<RegistryValue, String> {
+    let path = resolve_path(probe, path)?;
+    probe.read_value(path.hive, view, &path.subkey, value_name)
+}
+
+/// Write a value to the registry using full path
+/// Returns Ok(()) on success, Err with message on failure
+pub fn write_value(
+    probe: &dyn SystemProbe,
+    path: &str,
+    view: RegistryView,
+    value_name: &str,
+    value: &RegistryValue,
+) -> Result<(), String> {
+    let path = resolve_path(probe, path)?;
+    probe.write_value(path.hive, view, &path.subkey, value_name, value)
+}
@@ -0,0 +1,52 @@
+//! Windows service state and start type checks
+
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, ServiceCheck};
+use crate::probe::{ServiceStartType, ServiceState, SystemProbe, SERVICE_NOT_FOUND};
+
+/// Check a service's run state and/or start type
+///
+/// A service that isn't installed passes when the check only asks for it to
+/// be stopped or disabled.
+pub fn check(probe: &dyn SystemProbe, config: &CheckConfig, check: &ServiceCheck) -> CheckResult {
+    let expected = describe(check.service_state, check.start_type);
+
+    let status = match probe.service_status(&check.service_name) {
+        Ok(status) => status,
+        Err(e) if e == SERVICE_NOT_FOUND => {
+            let wants_off = check.service_state.is_none_or(|s| s == ServiceState::Stopped)
+                && check.start_type.is_none_or(|t| t == ServiceStartType::Disabled);
+            return if wants_off {
+                CheckResult::pass(&config.id, &config.name, "Not installed", &expected)
+            } else {
+                CheckResult::fail(&config.id, &config.name, "Not installed", &expected)
+            };
+        }
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+
+    // Report the same parts the check asks about
+    let current = describe(
+        check.service_state.map(|_| status.state),
+        check.start_type.map(|_| status.start_type),
+    );
+
+    let state_ok = check.service_state.is_none_or(|s| s == status.state);
+    let start_ok = check.start_type.is_none_or(|t| t == status.start_type);
+
+    if state_ok && start_ok {
+        CheckResult::pass(&config.id, &config.name, &current, &expected)
+    } else {
+        CheckResult::fail(&config.id, &config.name, &current, &expected)
+    }
+}
+
+/// "Stopped, Disabled", "Running" or "Manual"
+fn describe(state: Option<ServiceState>, start_type: Option<ServiceStartType>) -> String {
+    let parts: Vec<String> = state
+        .map(|s| s.to_string())
+        .into_iter()
+        .chain(start_type.map(|t| t.to_string()))
+        .collect();
+    parts.join(", ")
+}
@@ -0,0 +1,49 @@
+//! Task Scheduler checks: vendor updaters and maintenance tasks that fire mid-run
+
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, TaskCheck};
+use crate::probe::{SystemProbe, TaskStatus, TASK_NOT_FOUND};
+
+/// Read a task's status; a missing task can't fire, so it passes either check
+fn check_task(
+    probe: &dyn SystemProbe,
+    config: &CheckConfig,
+    check: &TaskCheck,
+    expected: &str,
+    current: impl Fn(TaskStatus) -> (&'static str, bool),
+) -> CheckResult {
+    match probe.scheduled_task(&check.task_name) {
+        Ok(status) => {
+            let (current, passed) = current(status);
+            if passed {
+                CheckResult::pass(&config.id, &config.name, current, expected)
+            } else {
+                CheckResult::fail(&config.id, &config.name, current, expected)
+            }
+        }
+        Err(e) if e == TASK_NOT_FOUND => CheckResult::pass(&config.id, &config.name, "Not found", expected),
+        Err(e) => CheckResult::error(&config.id, &config.name, &e),
+    }
+}
+
+/// Check that a scheduled task is disabled
+pub fn check_disabled(probe: &dyn SystemProbe, config: &CheckConfig, check: &TaskCheck) -> CheckResult {
+    check_task(probe, config, check, "Disabled", |status| {
+        if status.enabled {
+            ("Enabled", false)
+        } else {
+            ("Disabled", true)
+        }
+    })
+}
+
+/// Check that no instance of a scheduled task is running
+pub fn check_not_running(probe: &dyn SystemProbe, config: &CheckConfig, check: &TaskCheck) -> CheckResult {
+    check_task(probe, config, check, "Not Running", |status| {
+        if status.running {
+            ("Running", false)
+        } else {
+            ("Not Running", true)
+        }
+    })
+}
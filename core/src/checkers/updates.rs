@@ -0,0 +1,104 @@
+use crate::checkers::CheckResult;
+use crate::config::{CheckConfig, ValueCheck};
+use crate::probe::{RegistryHive, RegistryValue, RegistryView, SystemProbe, KEY_NOT_FOUND, VALUE_NOT_FOUND};
+
+/// Registry markers Windows leaves while a reboot is pending, as (source, HKLM
+/// subkey, value name); an empty value name means the key itself is the marker
+const REBOOT_MARKERS: &[(&str, &str, &str)] = &[
+    (
+        "component servicing",
+        "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Component Based Servicing\\RebootPending",
+        "",
+    ),
+    (
+        "Windows Update",
+        "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\WindowsUpdate\\Auto Update\\RebootRequired",
+        "",
+    ),
+    (
+        "pending file renames",
+        "SYSTEM\\CurrentControlSet\\Control\\Session Manager",
+        "PendingFileRenameOperations",
+    ),
+];
+
+/// Processes that only run while Windows Update or servicing is busy, as
+/// (process name, what it is doing)
+const UPDATE_PROCESSES: &[(&str, &str)] = &[
+    ("TiWorker.exe", "installing"),
+    ("TrustedInstaller.exe", "installing"),
+    ("MoUsoCoreWorker.exe", "scanning or downloading"),
+    ("usocoreworker.exe", "scanning or downloading"),
+    ("wuauclt.exe", "scanning or downloading"),
+];
+
+/// Whether a reboot marker is set (an empty rename list doesn't count)
+fn marker_set(probe: &dyn SystemProbe, subkey: &str, value_name: &str) -> Result<bool, String> {
+    match probe.read_value(RegistryHive::LocalMachine, RegistryView::Native, subkey, value_name) {
+        Ok(RegistryValue::MultiString(entries)) => Ok(entries.iter().any(|e| !e.is_empty())),
+        Ok(_) => Ok(true),
+        Err(e) if e == VALUE_NOT_FOUND => Ok(value_name.is_empty()),
+        Err(e) if e == KEY_NOT_FOUND => Ok(false),
+        Err(e) => Err(e),
+    }
+}
+
+/// Check for a pending reboot: "0" for none pending, "1" for pending
+///
+/// The current value names the components waiting for the reboot.
+pub fn check_pending_reboot(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = &check.expected_value;
+    let expected_display =
+        expected.describe(&|v| if v == "1" { "Reboot pending" } else { "No reboot pending" }.to_string());
+
+    let mut sources = Vec::new();
+    for &(source, subkey, value_name) in REBOOT_MARKERS {
+        match marker_set(probe, subkey, value_name) {
+            Ok(true) => sources.push(source),
+            Ok(false) => {}
+            Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+        }
+    }
+
+    let current = if sources.is_empty() {
+        "No reboot pending".to_string()
+    } else {
+        format!("Reboot pending ({})", sources.join(", "))
+    };
+    if expected.matches(if sources.is_empty() { "0" } else { "1" }) {
+        CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+    } else {
+        CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+    }
+}
+
+/// Check whether Windows Update is working: "0" for idle, "1" for active
+///
+/// Activity is read from the update and servicing worker processes, so the
+/// current value says what is running and what it is doing.
+pub fn check_update_active(probe: &dyn SystemProbe, config: &CheckConfig, check: &ValueCheck) -> CheckResult {
+    let expected = &check.expected_value;
+    let expected_display = expected.describe(&|v| if v == "1" { "Active" } else { "Idle" }.to_string());
+
+    let processes = match probe.running_processes() {
+        Ok(processes) => processes,
+        Err(e) => return CheckResult::error(&config.id, &config.name, &e),
+    };
+    let mut active: Vec<String> = Vec::new();
+    for &(name, activity) in UPDATE_PROCESSES {
+        if processes.iter().any(|p| p.name.eq_ignore_ascii_case(name)) {
+            active.push(format!("{} ({})", name, activity));
+        }
+    }
+
+    let current = if active.is_empty() {
+        "Idle".to_string()
+    } else {
+        format!("Active: {}", active.join(", "))
+    };
+    if expected.matches(if active.is_empty() { "0" } else { "1" }) {
+        CheckResult::pass(&config.id, &config.name, &current, &expected_display)
+    } else {
+        CheckResult::fail(&config.id, &config.name, &current, &expected_display)
+    }
+}
@@ -0,0 +1,1170 @@
+use crate::checkers::disks::FreeSpaceUnit;
+use crate::checkers::display::DisplayTarget;
+use crate::checkers::expectation::Expectation;
+use crate::checkers::power_plan::PowerSetting;
+use crate::checkers::processes::ProcessPattern;
+use crate::probe::{PowerSource, RegistryValueType, RegistryView, ServiceStartType, ServiceState};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+/// Configuration root - supports both v1 (flat) and v2 (nested scenarios) formats
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ConfigRoot {
+    V2(ConfigV2),
+    V1(ConfigV1),
+}
+
+impl ConfigRoot {
+    /// Parse either format, picking it by the presence of `scenarios` so errors
+    /// inside checks are reported instead of "did not match any variant"
+    pub fn parse(content: &str) -> serde_json::Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(content)?;
+        if value.get("scenarios").is_some() {
+            serde_json::from_str(content).map(ConfigRoot::V2)
+        } else {
+            serde_json::from_str(content).map(ConfigRoot::V1)
+        }
+    }
+}
+
+/// Legacy flat configuration (v1)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigV1 {
+    pub poll_interval_seconds: u64,
+    pub notify_on_drift: bool,
+    pub checks: Vec<CheckConfig>,
+}
+
+/// New scenario-based configuration (v2)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigV2 {
+    pub version: u32,
+    pub default_scenario: String,
+    pub scenarios: HashMap<String, Scenario>,
+}
+
+/// Individual scenario configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    pub description: String,
+    pub poll_interval_seconds: u64,
+    pub notify_on_drift: bool,
+    pub checks: Vec<CheckConfig>,
+}
+
+/// Working configuration (what the application uses internally)
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub root: ConfigV2,
+    pub active_scenario: String,
+}
+
+/// Individual check configuration
+///
+/// Serialized flat: `id`, `name`, `enabled`, `check_type` and the fields that
+/// check type takes. Missing or irrelevant fields are rejected at load time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawCheckConfig")]
+pub struct CheckConfig {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub enabled: bool,
+    /// Skip the check (reported as skipped, not passed) on desktops
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub laptop_only: bool,
+    #[serde(flatten)]
+    pub kind: CheckKind,
+}
+
+/// What a check looks at, tagged by `check_type`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "check_type", rename_all = "snake_case")]
+pub enum CheckKind {
+    PowerScheme(ValueCheck),
+    PowerMode(ValueCheck),
+    RegistryDword(RegistryCheck),
+    RegistryQword(RegistryCheck),
+    RegistryString(RegistryCheck),
+    RegistryExpandString(RegistryCheck),
+    RegistryMultiString(RegistryCheck),
+    RegistryBinary(RegistryCheck),
+    RegistryExists(RegistryExistenceCheck),
+    RegistryAbsent(RegistryExistenceCheck),
+    ProcessAbsent(ProcessCheck),
+    ProcessPresent(ProcessCheck),
+    DisplayResolution(DisplayCheck),
+    DisplayRefreshRate(DisplayCheck),
+    DisplayCount(ValueCheck),
+    DisplayBitDepth(DisplayCheck),
+    DisplayScaling(DisplayCheck),
+    DisplayOrientation(DisplayCheck),
+    HdrEnabled(DisplayCheck),
+    PowerSetting(PowerSettingCheck),
+    ServiceState(ServiceCheck),
+    TaskDisabled(TaskCheck),
+    TaskNotRunning(TaskCheck),
+    AcPower(ValueCheck),
+    BatteryLevel(ValueCheck),
+    CpuIdle(CpuIdleCheck),
+    MemoryAvailable(ValueCheck),
+    CommitCharge(ValueCheck),
+    Pagefile(ValueCheck),
+    DiskFree(DiskFreeCheck),
+    DriveType(DriveTypeCheck),
+    PendingReboot(ValueCheck),
+    WindowsUpdateActive(ValueCheck),
+}
+
+/// Fields for checks that compare a single system value
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValueCheck {
+    pub expected_value: Expectation,
+}
+
+/// Fields for checks on one display's settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DisplayCheck {
+    pub expected_value: Expectation,
+    /// `primary`, a device name (`\\.\DISPLAY2`) or a monitor model; primary when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayTarget>,
+}
+
+/// Fields for registry value checks
+///
+/// `expected_value` literals are written as the value's text: numbers for
+/// DWORD/QWORD (decimal or `0x` hex), `;`-separated entries for multi-strings
+/// and hex bytes (e.g. `"01 00 ff"`) for binary data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryCheck {
+    pub registry_path: String,
+    pub registry_key: String,
+    pub expected_value: Expectation,
+    /// `"32"` or `"64"` to read a specific registry view
+    #[serde(default, skip_serializing_if = "RegistryView::is_native")]
+    pub registry_view: RegistryView,
+}
+
+/// Fields for registry existence checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RegistryExistenceCheck {
+    pub registry_path: String,
+    /// Value to look for; without one the key itself is checked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_key: Option<String>,
+    #[serde(default, skip_serializing_if = "RegistryView::is_native")]
+    pub registry_view: RegistryView,
+}
+
+/// Fields for process checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProcessCheck {
+    /// Executable name: exact, glob (`Razer*.exe`) or `regex: ...`
+    pub process_name: ProcessPattern,
+    /// Full image path pattern, to tell apart executables with the same name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub process_path: Option<ProcessPattern>,
+    /// Fewest matching instances allowed (default 1 for process_present)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_instances: Option<u32>,
+    /// Most matching instances allowed (default 0 for process_absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_instances: Option<u32>,
+}
+
+/// Fields for checks on background CPU load
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CpuIdleCheck {
+    /// Busy percentage of all processors (a bare number is a maximum)
+    pub expected_value: Expectation,
+    /// Shortest time to measure load over (default 3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_seconds: Option<u32>,
+}
+
+/// Fields for free space checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DiskFreeCheck {
+    /// Drive letter (`D:`) or any path on the volume
+    pub path: String,
+    /// Free space to require (a bare number is a minimum)
+    pub expected_value: Expectation,
+    /// `gb` (default) or `percent` of the volume
+    #[serde(default, skip_serializing_if = "FreeSpaceUnit::is_gb")]
+    pub unit: FreeSpaceUnit,
+}
+
+/// Fields for checks on the kind of drive a path lives on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DriveTypeCheck {
+    /// Drive letter (`D:`) or any path on the volume
+    pub path: String,
+    pub expected_value: Expectation,
+}
+
+/// Fields for checks on a setting inside the active power scheme
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PowerSettingCheck {
+    /// Alias (`processor_boost_mode`) or `SUBGROUP_GUID/SETTING_GUID`
+    pub power_setting: PowerSetting,
+    /// Compared against the setting's value index
+    pub expected_value: Expectation,
+    /// Check only the `ac` or `dc` value; both when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_source: Option<PowerSource>,
+}
+
+/// Fields for service checks; at least one of the state and start type is set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceCheck {
+    /// Service (not display) name, e.g. `SysMain`
+    pub service_name: String,
+    /// Expected run state: `running` or `stopped`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service_state: Option<ServiceState>,
+    /// Expected start type: `disabled`, `manual` or `automatic` (`auto`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_type: Option<ServiceStartType>,
+}
+
+/// Fields for scheduled task checks
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskCheck {
+    /// Full task path, e.g. `\Microsoft\Windows\Defrag\ScheduledDefrag`
+    pub task_name: String,
+}
+
+/// Shape of a check in the config file, before its fields are checked
+/// against its `check_type`
+#[derive(Deserialize)]
+struct RawCheckConfig {
+    id: String,
+    name: String,
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    laptop_only: bool,
+    #[serde(flatten)]
+    fields: serde_json::Map<String, serde_json::Value>,
+}
+
+impl TryFrom<RawCheckConfig> for CheckConfig {
+    type Error = String;
+
+    fn try_from(raw: RawCheckConfig) -> std::result::Result<Self, String> {
+        // Deserializing the kind separately lets the error name the check
+        let kind = CheckKind::deserialize(serde_json::Value::Object(raw.fields))
+            .map_err(|e| format!("check '{}': {}", raw.id, e))?;
+
+        Ok(CheckConfig {
+            id: raw.id,
+            name: raw.name,
+            enabled: raw.enabled,
+            laptop_only: raw.laptop_only,
+            kind,
+        })
+    }
+}
+
+impl CheckConfig {
+    /// Create an enabled check
+    pub fn new(id: &str, name: &str, kind: CheckKind) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            enabled: true,
+            laptop_only: false,
+            kind,
+        }
+    }
+
+    pub fn check_type(&self) -> CheckType {
+        self.kind.check_type()
+    }
+
+    /// Find problems with values that would make this check error or never pass
+    /// (missing and unknown fields are already rejected when loading)
+    pub fn validate(&self) -> Vec<String> {
+        use crate::checkers::{disks, display, memory, power_plan, registry};
+
+        let mut problems = Vec::new();
+
+        match &self.kind {
+            CheckKind::PowerScheme(check) => {
+                // Any built-in key, GUID or plan name is allowed; a plan
+                // that isn't installed here may be on the bench machine
+                if check.expected_value.is_ordered() {
+                    problems.push("power schemes can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::PowerMode(check) => {
+                for v in check.expected_value.literals() {
+                    if !power_plan::is_known_mode(v) {
+                        problems.push(format!("unknown power mode '{}'", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("power modes can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::RegistryDword(check)
+            | CheckKind::RegistryQword(check)
+            | CheckKind::RegistryString(check)
+            | CheckKind::RegistryExpandString(check)
+            | CheckKind::RegistryMultiString(check)
+            | CheckKind::RegistryBinary(check) => {
+                if let Err(e) = registry::validate_path(&check.registry_path) {
+                    problems.push(e);
+                }
+                if let Some(value_type) = self.kind.registry_value_type() {
+                    for v in check.expected_value.literals() {
+                        if let Err(e) = registry::parse_value(value_type, v) {
+                            problems.push(format!("expected_value {}", e));
+                        }
+                    }
+                }
+            }
+            CheckKind::RegistryExists(check) | CheckKind::RegistryAbsent(check) => {
+                if let Err(e) = registry::validate_path(&check.registry_path) {
+                    problems.push(e);
+                }
+            }
+            CheckKind::ProcessAbsent(check) | CheckKind::ProcessPresent(check) => {
+                if check.process_name.as_str().is_empty() {
+                    problems.push("process_name is empty".to_string());
+                }
+                if let (Some(min), Some(max)) = (check.min_instances, check.max_instances) {
+                    if min > max {
+                        problems.push(format!("min_instances {} is greater than max_instances {}", min, max));
+                    }
+                }
+            }
+            CheckKind::DisplayResolution(check) => {
+                let valid = |v: &str| {
+                    v.split_once('x')
+                        .map(|(w, h)| w.parse::<u32>().is_ok() && h.parse::<u32>().is_ok())
+                        .unwrap_or(false)
+                };
+                for v in check.expected_value.literals() {
+                    if !valid(v) {
+                        problems.push(format!("expected_value '{}' is not a WIDTHxHEIGHT resolution", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("resolutions can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::DisplayRefreshRate(check) => {
+                for v in check.expected_value.literals() {
+                    if v.parse::<u32>().is_err() {
+                        problems.push(format!("expected_value '{}' is not a refresh rate in Hz", v));
+                    }
+                }
+            }
+            CheckKind::DisplayCount(check) => {
+                for v in check.expected_value.literals() {
+                    if v.parse::<u32>().is_err() {
+                        problems.push(format!("expected_value '{}' is not a number of displays", v));
+                    }
+                }
+            }
+            CheckKind::DisplayBitDepth(check) => {
+                for v in check.expected_value.literals() {
+                    if v.parse::<u32>().is_err() {
+                        problems.push(format!("expected_value '{}' is not a number of bits per channel", v));
+                    }
+                }
+            }
+            CheckKind::DisplayScaling(check) => {
+                for v in check.expected_value.literals() {
+                    if v.parse::<u32>().is_err() {
+                        problems.push(format!("expected_value '{}' is not a scaling percentage", v));
+                    }
+                }
+            }
+            CheckKind::DisplayOrientation(check) => {
+                for v in check.expected_value.literals() {
+                    if display::parse_orientation(v).is_none() {
+                        problems.push(format!("unknown display orientation '{}'", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("orientations can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::HdrEnabled(check) => {
+                for v in check.expected_value.literals() {
+                    if v != "0" && v != "1" {
+                        problems.push(format!("expected_value '{}' must be 0 or 1", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("HDR state can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::PowerSetting(check) => {
+                for v in check.expected_value.literals() {
+                    if v.parse::<u32>().is_err() {
+                        problems.push(format!("expected_value '{}' is not a power setting value index", v));
+                    }
+                }
+            }
+            CheckKind::ServiceState(check) => {
+                if check.service_name.trim().is_empty() {
+                    problems.push("service_name is empty".to_string());
+                }
+                if check.service_state.is_none() && check.start_type.is_none() {
+                    problems.push("service_state or start_type is required".to_string());
+                }
+                if let Some(state @ (ServiceState::Paused | ServiceState::Pending)) = check.service_state {
+                    problems.push(format!("service_state must be running or stopped, not {}", state));
+                }
+                if check.service_state == Some(ServiceState::Running)
+                    && check.start_type == Some(ServiceStartType::Disabled)
+                {
+                    problems.push("a disabled service can't be expected to be running".to_string());
+                }
+            }
+            CheckKind::TaskDisabled(check) | CheckKind::TaskNotRunning(check) => {
+                if check.task_name.trim().is_empty() {
+                    problems.push("task_name is empty".to_string());
+                }
+            }
+            CheckKind::AcPower(check) => {
+                for v in check.expected_value.literals() {
+                    if v != "0" && v != "1" {
+                        problems.push(format!("expected_value '{}' must be 0 or 1", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("AC power state can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::BatteryLevel(check) => {
+                for v in check.expected_value.literals() {
+                    if !v.parse::<u32>().is_ok_and(|p| p <= 100) {
+                        problems.push(format!("expected_value '{}' is not a battery percentage", v));
+                    }
+                }
+            }
+            CheckKind::CpuIdle(check) => {
+                for v in check.expected_value.literals() {
+                    if !v.parse::<f64>().is_ok_and(|p| (0.0..=100.0).contains(&p)) {
+                        problems.push(format!("expected_value '{}' is not a CPU percentage", v));
+                    }
+                }
+                if let Some(seconds @ (0 | 31..)) = check.sample_seconds {
+                    problems.push(format!("sample_seconds {} must be between 1 and 30", seconds));
+                }
+            }
+            CheckKind::MemoryAvailable(check) => {
+                for v in check.expected_value.literals() {
+                    if !v.parse::<f64>().is_ok_and(|gb| gb.is_finite() && gb >= 0.0) {
+                        problems.push(format!("expected_value '{}' is not an amount of memory in GB", v));
+                    }
+                }
+            }
+            CheckKind::CommitCharge(check) => {
+                for v in check.expected_value.literals() {
+                    if !v.parse::<f64>().is_ok_and(|p| (0.0..=100.0).contains(&p)) {
+                        problems.push(format!("expected_value '{}' is not a commit charge percentage", v));
+                    }
+                }
+            }
+            CheckKind::Pagefile(check) => {
+                for v in check.expected_value.literals() {
+                    if memory::parse_pagefile_mode(v).is_none() {
+                        problems.push(format!("unknown pagefile mode '{}'", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("pagefile modes can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::DiskFree(check) => {
+                if check.path.trim().is_empty() {
+                    problems.push("path is empty".to_string());
+                }
+                for v in check.expected_value.literals() {
+                    if !v.parse::<f64>().is_ok_and(|n| n.is_finite() && n >= 0.0) {
+                        problems.push(format!("expected_value '{}' is not an amount of free space", v));
+                    }
+                }
+            }
+            CheckKind::DriveType(check) => {
+                if check.path.trim().is_empty() {
+                    problems.push("path is empty".to_string());
+                }
+                for v in check.expected_value.literals() {
+                    if disks::parse_drive_kind(v).is_none() {
+                        problems.push(format!("unknown drive type '{}'", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("drive types can't be compared numerically".to_string());
+                }
+            }
+            CheckKind::PendingReboot(check) | CheckKind::WindowsUpdateActive(check) => {
+                for v in check.expected_value.literals() {
+                    if v != "0" && v != "1" {
+                        problems.push(format!("expected_value '{}' must be 0 or 1", v));
+                    }
+                }
+                if check.expected_value.is_ordered() {
+                    problems.push("expected_value can't be compared numerically".to_string());
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+impl CheckKind {
+    pub fn check_type(&self) -> CheckType {
+        match self {
+            CheckKind::PowerScheme(_) => CheckType::PowerScheme,
+            CheckKind::PowerMode(_) => CheckType::PowerMode,
+            CheckKind::RegistryDword(_) => CheckType::RegistryDword,
+            CheckKind::RegistryQword(_) => CheckType::RegistryQword,
+            CheckKind::RegistryString(_) => CheckType::RegistryString,
+            CheckKind::RegistryExpandString(_) => CheckType::RegistryExpandString,
+            CheckKind::RegistryMultiString(_) => CheckType::RegistryMultiString,
+            CheckKind::RegistryBinary(_) => CheckType::RegistryBinary,
+            CheckKind::RegistryExists(_) => CheckType::RegistryExists,
+            CheckKind::RegistryAbsent(_) => CheckType::RegistryAbsent,
+            CheckKind::ProcessAbsent(_) => CheckType::ProcessAbsent,
+            CheckKind::ProcessPresent(_) => CheckType::ProcessPresent,
+            CheckKind::DisplayResolution(_) => CheckType::DisplayResolution,
+            CheckKind::DisplayRefreshRate(_) => CheckType::DisplayRefreshRate,
+            CheckKind::DisplayCount(_) => CheckType::DisplayCount,
+            CheckKind::DisplayBitDepth(_) => CheckType::DisplayBitDepth,
+            CheckKind::DisplayScaling(_) => CheckType::DisplayScaling,
+            CheckKind::DisplayOrientation(_) => CheckType::DisplayOrientation,
+            CheckKind::HdrEnabled(_) => CheckType::HdrEnabled,
+            CheckKind::PowerSetting(_) => CheckType::PowerSetting,
+            CheckKind::ServiceState(_) => CheckType::ServiceState,
+            CheckKind::TaskDisabled(_) => CheckType::TaskDisabled,
+            CheckKind::TaskNotRunning(_) => CheckType::TaskNotRunning,
+            CheckKind::AcPower(_) => CheckType::AcPower,
+            CheckKind::BatteryLevel(_) => CheckType::BatteryLevel,
+            CheckKind::CpuIdle(_) => CheckType::CpuIdle,
+            CheckKind::MemoryAvailable(_) => CheckType::MemoryAvailable,
+            CheckKind::CommitCharge(_) => CheckType::CommitCharge,
+            CheckKind::Pagefile(_) => CheckType::Pagefile,
+            CheckKind::DiskFree(_) => CheckType::DiskFree,
+            CheckKind::DriveType(_) => CheckType::DriveType,
+            CheckKind::PendingReboot(_) => CheckType::PendingReboot,
+            CheckKind::WindowsUpdateActive(_) => CheckType::WindowsUpdateActive,
+        }
+    }
+
+    /// The value type a registry check expects, None for other checks
+    pub fn registry_value_type(&self) -> Option<RegistryValueType> {
+        match self {
+            CheckKind::RegistryDword(_) => Some(RegistryValueType::Dword),
+            CheckKind::RegistryQword(_) => Some(RegistryValueType::Qword),
+            CheckKind::RegistryString(_) => Some(RegistryValueType::String),
+            CheckKind::RegistryExpandString(_) => Some(RegistryValueType::ExpandString),
+            CheckKind::RegistryMultiString(_) => Some(RegistryValueType::MultiString),
+            CheckKind::RegistryBinary(_) => Some(RegistryValueType::Binary),
+            _ => None,
+        }
+    }
+
+    /// Build a check from loosely-typed fields (editor form, check library),
+    /// taking the fields `check_type` needs and ignoring the rest
+    pub fn from_fields(
+        check_type: CheckType,
+        field: impl Fn(&str) -> Option<String>,
+    ) -> std::result::Result<Self, String> {
+        let require = |name: &str| {
+            field(name)
+                .filter(|v| !v.trim().is_empty())
+                .ok_or_else(|| format!("missing {}", name))
+        };
+        let value = || -> std::result::Result<ValueCheck, String> {
+            Ok(ValueCheck {
+                expected_value: Expectation::parse(&require("expected_value")?)?,
+            })
+        };
+        let display = || -> std::result::Result<DisplayCheck, String> {
+            Ok(DisplayCheck {
+                expected_value: Expectation::parse(&require("expected_value")?)?,
+                display: require("display").ok().map(|d| DisplayTarget::parse(&d)).transpose()?,
+            })
+        };
+        let view = || field("registry_view").unwrap_or_default().parse::<RegistryView>();
+        let registry = || -> std::result::Result<RegistryCheck, String> {
+            Ok(RegistryCheck {
+                registry_path: require("registry_path")?,
+                registry_key: require("registry_key")?,
+                expected_value: Expectation::parse(&require("expected_value")?)?,
+                registry_view: view()?,
+            })
+        };
+        let existence = || -> std::result::Result<RegistryExistenceCheck, String> {
+            Ok(RegistryExistenceCheck {
+                registry_path: require("registry_path")?,
+                registry_key: require("registry_key").ok(),
+                registry_view: view()?,
+            })
+        };
+        let count = |name: &str| -> std::result::Result<Option<u32>, String> {
+            require(name)
+                .ok()
+                .map(|v| v.trim().parse().map_err(|_| format!("{} must be a whole number", name)))
+                .transpose()
+        };
+        let process = || -> std::result::Result<ProcessCheck, String> {
+            Ok(ProcessCheck {
+                process_name: ProcessPattern::parse(&require("process_name")?)?,
+                process_path: require("process_path").ok().map(|p| ProcessPattern::parse(&p)).transpose()?,
+                min_instances: count("min_instances")?,
+                max_instances: count("max_instances")?,
+            })
+        };
+
+        // Enum fields parse the same way as in the config file
+        fn parse_enum<T: serde::de::DeserializeOwned>(
+            name: &str,
+            value: Option<String>,
+        ) -> std::result::Result<Option<T>, String> {
+            value
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| {
+                    serde_json::from_value(serde_json::Value::String(v.trim().to_lowercase()))
+                        .map_err(|_| format!("invalid {} '{}'", name, v))
+                })
+                .transpose()
+        }
+        let service = || -> std::result::Result<ServiceCheck, String> {
+            Ok(ServiceCheck {
+                service_name: require("service_name")?,
+                service_state: parse_enum("service_state", field("service_state"))?,
+                start_type: parse_enum("start_type", field("start_type"))?,
+            })
+        };
+        let power_setting = || -> std::result::Result<PowerSettingCheck, String> {
+            Ok(PowerSettingCheck {
+                power_setting: PowerSetting::parse(&require("power_setting")?)?,
+                expected_value: Expectation::parse(&require("expected_value")?)?,
+                power_source: parse_enum("power_source", field("power_source"))?,
+            })
+        };
+        let task = || -> std::result::Result<TaskCheck, String> {
+            Ok(TaskCheck {
+                task_name: require("task_name")?,
+            })
+        };
+
+        Ok(match check_type {
+            CheckType::PowerScheme => CheckKind::PowerScheme(value()?),
+            CheckType::PowerMode => CheckKind::PowerMode(value()?),
+            CheckType::RegistryDword => CheckKind::RegistryDword(registry()?),
+            CheckType::RegistryQword => CheckKind::RegistryQword(registry()?),
+            CheckType::RegistryString => CheckKind::RegistryString(registry()?),
+            CheckType::RegistryExpandString => CheckKind::RegistryExpandString(registry()?),
+            CheckType::RegistryMultiString => CheckKind::RegistryMultiString(registry()?),
+            CheckType::RegistryBinary => CheckKind::RegistryBinary(registry()?),
+            CheckType::RegistryExists => CheckKind::RegistryExists(existence()?),
+            CheckType::RegistryAbsent => CheckKind::RegistryAbsent(existence()?),
+            CheckType::ProcessAbsent => CheckKind::ProcessAbsent(process()?),
+            CheckType::ProcessPresent => CheckKind::ProcessPresent(process()?),
+            CheckType::DisplayResolution => CheckKind::DisplayResolution(display()?),
+            CheckType::DisplayRefreshRate => CheckKind::DisplayRefreshRate(display()?),
+            CheckType::DisplayCount => CheckKind::DisplayCount(value()?),
+            CheckType::DisplayBitDepth => CheckKind::DisplayBitDepth(display()?),
+            CheckType::DisplayScaling => CheckKind::DisplayScaling(display()?),
+            CheckType::DisplayOrientation => CheckKind::DisplayOrientation(display()?),
+            CheckType::HdrEnabled => CheckKind::HdrEnabled(display()?),
+            CheckType::PowerSetting => CheckKind::PowerSetting(power_setting()?),
+            CheckType::ServiceState => CheckKind::ServiceState(service()?),
+            CheckType::TaskDisabled => CheckKind::TaskDisabled(task()?),
+            CheckType::TaskNotRunning => CheckKind::TaskNotRunning(task()?),
+            CheckType::AcPower => CheckKind::AcPower(value()?),
+            CheckType::BatteryLevel => CheckKind::BatteryLevel(value()?),
+            CheckType::CpuIdle => CheckKind::CpuIdle(CpuIdleCheck {
+                expected_value: Expectation::parse(&require("expected_value")?)?,
+                sample_seconds: count("sample_seconds")?,
+            }),
+            CheckType::MemoryAvailable => CheckKind::MemoryAvailable(value()?),
+            CheckType::CommitCharge => CheckKind::CommitCharge(value()?),
+            CheckType::Pagefile => CheckKind::Pagefile(value()?),
+            CheckType::DiskFree => CheckKind::DiskFree(DiskFreeCheck {
+                path: require("path")?,
+                expected_value: Expectation::parse(&require("expected_value")?)?,
+                unit: parse_enum("unit", field("unit"))?.unwrap_or_default(),
+            }),
+            CheckType::DriveType => CheckKind::DriveType(DriveTypeCheck {
+                path: require("path")?,
+                expected_value: Expectation::parse(&require("expected_value")?)?,
+            }),
+            CheckType::PendingReboot => CheckKind::PendingReboot(value()?),
+            CheckType::WindowsUpdateActive => CheckKind::WindowsUpdateActive(value()?),
+        })
+    }
+
+    /// Look up one of this check's fields by its config name (for the editor form)
+    pub fn field(&self, name: &str) -> Option<String> {
+        match serde_json::to_value(self).ok()?.get(name)? {
+            serde_json::Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+}
+
+/// Types of checks supported
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckType {
+    PowerScheme,
+    PowerMode,
+    RegistryDword,
+    RegistryQword,
+    RegistryString,
+    RegistryExpandString,
+    RegistryMultiString,
+    RegistryBinary,
+    RegistryExists,
+    RegistryAbsent,
+    ProcessAbsent,
+    ProcessPresent,
+    DisplayResolution,
+    DisplayRefreshRate,
+    DisplayCount,
+    DisplayBitDepth,
+    DisplayScaling,
+    DisplayOrientation,
+    HdrEnabled,
+    PowerSetting,
+    ServiceState,
+    TaskDisabled,
+    TaskNotRunning,
+    AcPower,
+    BatteryLevel,
+    CpuIdle,
+    MemoryAvailable,
+    CommitCharge,
+    Pagefile,
+    DiskFree,
+    DriveType,
+    PendingReboot,
+    WindowsUpdateActive,
+}
+
+/// Helpers for the default scenarios below
+fn power_scheme_check(id: &str, name: &str, scheme: &str) -> CheckConfig {
+    CheckConfig::new(
+        id,
+        name,
+        CheckKind::PowerScheme(ValueCheck {
+            expected_value: Expectation::Equals(scheme.to_string()),
+        }),
+    )
+}
+
+fn power_mode_check(id: &str, name: &str, mode: &str) -> CheckConfig {
+    CheckConfig::new(
+        id,
+        name,
+        CheckKind::PowerMode(ValueCheck {
+            expected_value: Expectation::Equals(mode.to_string()),
+        }),
+    )
+}
+
+fn registry_dword_check(id: &str, name: &str, path: &str, key: &str, value: u32) -> CheckConfig {
+    CheckConfig::new(
+        id,
+        name,
+        CheckKind::RegistryDword(RegistryCheck {
+            registry_path: path.to_string(),
+            registry_key: key.to_string(),
+            expected_value: Expectation::Equals(value.to_string()),
+            registry_view: RegistryView::Native,
+        }),
+    )
+}
+
+fn process_absent_check(id: &str, name: &str, process_name: &str) -> CheckConfig {
+    CheckConfig::new(
+        id,
+        name,
+        CheckKind::ProcessAbsent(ProcessCheck {
+            process_name: ProcessPattern::parse(process_name).expect("default process name is a valid pattern"),
+            process_path: None,
+            min_instances: None,
+            max_instances: None,
+        }),
+    )
+}
+
+/// Helper functions to create default scenarios
+fn create_gaming_scenario() -> Scenario {
+    Scenario {
+        name: "Gaming Benchmarks".to_string(),
+        description: "Optimized for gaming performance testing".to_string(),
+        poll_interval_seconds: 5,
+        notify_on_drift: true,
+        checks: vec![
+            power_scheme_check("power_plan", "Power Plan (High Performance)", "high_performance"),
+            power_mode_check("power_mode", "Power Mode (Best Performance)", "best_performance"),
+            registry_dword_check(
+                "game_mode",
+                "Game Mode Enabled",
+                "HKCU\\Software\\Microsoft\\GameBar",
+                "AutoGameModeEnabled",
+                1,
+            ),
+            registry_dword_check(
+                "hardware_gpu_scheduling",
+                "Hardware GPU Scheduling",
+                "HKLM\\SYSTEM\\CurrentControlSet\\Control\\GraphicsDrivers",
+                "HwSchMode",
+                2,
+            ),
+            process_absent_check("no_discord", "Discord Not Running", "Discord.exe"),
+            process_absent_check("no_chrome", "Chrome Not Running", "chrome.exe"),
+        ],
+    }
+}
+
+fn create_cpu_scenario() -> Scenario {
+    Scenario {
+        name: "CPU Benchmarks".to_string(),
+        description: "Focused on CPU-intensive workloads".to_string(),
+        poll_interval_seconds: 10,
+        notify_on_drift: true,
+        checks: vec![
+            power_scheme_check("power_plan", "Power Plan (High Performance)", "high_performance"),
+            power_mode_check("power_mode", "Power Mode (Best Performance)", "best_performance"),
+            registry_dword_check(
+                "background_apps",
+                "Background Apps Disabled",
+                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\BackgroundAccessApplications",
+                "GlobalUserDisabled",
+                1,
+            ),
+            process_absent_check("no_chrome", "Chrome Not Running", "chrome.exe"),
+        ],
+    }
+}
+
+fn create_gpu_scenario() -> Scenario {
+    Scenario {
+        name: "GPU Benchmarks".to_string(),
+        description: "Optimized for GPU testing".to_string(),
+        poll_interval_seconds: 5,
+        notify_on_drift: true,
+        checks: vec![
+            power_scheme_check("power_plan", "Power Plan (High Performance)", "high_performance"),
+            power_mode_check("power_mode", "Power Mode (Best Performance)", "best_performance"),
+            registry_dword_check(
+                "hardware_gpu_scheduling",
+                "Hardware GPU Scheduling",
+                "HKLM\\SYSTEM\\CurrentControlSet\\Control\\GraphicsDrivers",
+                "HwSchMode",
+                2,
+            ),
+            registry_dword_check(
+                "visual_effects",
+                "Visual Effects (Best Performance)",
+                "HKCU\\Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\VisualEffects",
+                "VisualFXSetting",
+                2,
+            ),
+        ],
+    }
+}
+
+fn create_productivity_scenario() -> Scenario {
+    Scenario {
+        name: "Productivity Workloads".to_string(),
+        description: "For office and productivity testing".to_string(),
+        poll_interval_seconds: 15,
+        notify_on_drift: false,
+        checks: vec![power_scheme_check("power_plan", "Power Plan (Balanced)", "balanced")],
+    }
+}
+
+/// Migrate v1 config to v2 format
+fn migrate_v1_to_v2(v1: ConfigV1) -> ConfigV2 {
+    let scenario = Scenario {
+        name: "Default".to_string(),
+        description: "Migrated from legacy config".to_string(),
+        poll_interval_seconds: v1.poll_interval_seconds,
+        notify_on_drift: v1.notify_on_drift,
+        checks: v1.checks,
+    };
+
+    let mut scenarios = HashMap::new();
+    scenarios.insert("default".to_string(), scenario);
+
+    ConfigV2 {
+        version: 2,
+        default_scenario: "default".to_string(),
+        scenarios,
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        // Create all 4 default scenarios
+        let mut scenarios = HashMap::new();
+
+        scenarios.insert("gaming".to_string(), create_gaming_scenario());
+        scenarios.insert("cpu_benchmark".to_string(), create_cpu_scenario());
+        scenarios.insert("gpu_benchmark".to_string(), create_gpu_scenario());
+        scenarios.insert("productivity".to_string(), create_productivity_scenario());
+
+        let root = ConfigV2 {
+            version: 2,
+            default_scenario: "gaming".to_string(),
+            scenarios,
+        };
+
+        Self {
+            root,
+            active_scenario: "gaming".to_string(),
+        }
+    }
+}
+
+impl Config {
+    /// Get the default config file path
+    pub fn default_path() -> PathBuf {
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+            .unwrap_or_else(|| PathBuf::from("."));
+
+        exe_dir.join("config").join("checklist.json")
+    }
+
+    /// Load configuration from file (handles both v1 and v2 formats)
+    pub fn load(path: &PathBuf) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file: {:?}", path))?;
+
+        let root = ConfigRoot::parse(&content).with_context(|| "Failed to parse config JSON")?;
+
+        let config_v2 = match root {
+            ConfigRoot::V1(v1) => {
+                tracing::info!("Migrating v1 config to v2 format");
+                migrate_v1_to_v2(v1)
+            }
+            ConfigRoot::V2(v2) => v2,
+        };
+
+        let active_scenario = config_v2.default_scenario.clone();
+
+        // Validate active scenario exists
+        if !config_v2.scenarios.contains_key(&active_scenario) {
+            return Err(anyhow!(
+                "Default scenario '{}' not found in config",
+                active_scenario
+            ));
+        }
+
+        Ok(Config {
+            root: config_v2,
+            active_scenario,
+        })
+    }
+
+    /// Load from default path, or create default if not exists
+    pub fn load_or_default() -> Result<Self> {
+        let path = Self::default_path();
+
+        if path.exists() {
+            Self::load(&path)
+        } else {
+            let config = Config::default();
+            config.save(&path)?;
+            Ok(config)
+        }
+    }
+
+    /// Save configuration to file (always saves as v2, creates backup)
+    pub fn save(&self, path: &PathBuf) -> Result<()> {
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create config directory: {:?}", parent))?;
+        }
+
+        // Create backup if file exists
+        if path.exists() {
+            let backup_path = path.with_extension("json.backup");
+            fs::copy(path, &backup_path)
+                .with_context(|| format!("Failed to create backup at {:?}", backup_path))?;
+            tracing::info!("Created config backup at {:?}", backup_path);
+        }
+
+        // Update default_scenario to persist active scenario
+        let mut root = self.root.clone();
+        root.default_scenario = self.active_scenario.clone();
+
+        let content = serde_json::to_string_pretty(&root)
+            .with_context(|| "Failed to serialize config")?;
+
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write config file: {:?}", path))?;
+
+        Ok(())
+    }
+
+    /// Get active scenario data
+    pub fn get_active_scenario_data(&self) -> Result<&Scenario> {
+        self.root
+            .scenarios
+            .get(&self.active_scenario)
+            .ok_or_else(|| anyhow!("Active scenario '{}' not found", self.active_scenario))
+    }
+
+    /// Get checks from active scenario
+    pub fn get_scenario_checks(&self) -> Result<&Vec<CheckConfig>> {
+        Ok(&self.get_active_scenario_data()?.checks)
+    }
+
+    /// Get list of scenario IDs
+    pub fn get_scenario_ids(&self) -> Vec<String> {
+        self.root.scenarios.keys().cloned().collect()
+    }
+
+    /// Get only enabled checks from active scenario
+    pub fn enabled_checks(&self) -> Vec<&CheckConfig> {
+        if let Ok(checks) = self.get_scenario_checks() {
+            checks.iter().filter(|c| c.enabled).collect()
+        } else {
+            vec![]
+        }
+    }
+
+    /// Get poll interval from active scenario
+    pub fn get_poll_interval(&self) -> u64 {
+        self.get_active_scenario_data()
+            .map(|s| s.poll_interval_seconds)
+            .unwrap_or(10)
+    }
+
+    /// Get notify_on_drift from active scenario
+    pub fn get_notify_on_drift(&self) -> bool {
+        self.get_active_scenario_data()
+            .map(|s| s.notify_on_drift)
+            .unwrap_or(true)
+    }
+
+    /// Find problems in every scenario (duplicate IDs, missing or invalid fields)
+    /// Returns one message per problem; empty if the config is valid
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let mut scenario_ids: Vec<&String> = self.root.scenarios.keys().collect();
+        scenario_ids.sort();
+
+        for scenario_id in scenario_ids {
+            let scenario = &self.root.scenarios[scenario_id];
+            let mut seen = HashSet::new();
+
+            for check in &scenario.checks {
+                if !seen.insert(check.id.as_str()) {
+                    problems.push(format!(
+                        "scenario '{}': duplicate check id '{}'",
+                        scenario_id, check.id
+                    ));
+                }
+                for problem in check.validate() {
+                    problems.push(format!(
+                        "scenario '{}', check '{}': {}",
+                        scenario_id, check.id, problem
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_check(json: &str) -> std::result::Result<CheckConfig, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_check_fields_must_match_type() {
+        let check = parse_check(
+            r#"{"id": "no_chrome", "name": "Chrome", "check_type": "process_absent",
+                "enabled": true, "process_name": "chrome.exe"}"#,
+        )
+        .unwrap();
+        assert_eq!(check.check_type(), CheckType::ProcessAbsent);
+
+        let irrelevant = parse_check(
+            r#"{"id": "no_chrome", "name": "Chrome", "check_type": "process_absent",
+                "process_name": "chrome.exe", "registry_path": "HKCU\\Software"}"#,
+        )
+        .unwrap_err();
+        assert!(irrelevant.contains("check 'no_chrome'"), "{}", irrelevant);
+        assert!(irrelevant.contains("unknown field `registry_path`"), "{}", irrelevant);
+
+        let missing = parse_check(
+            r#"{"id": "res", "name": "Resolution", "check_type": "display_resolution"}"#,
+        )
+        .unwrap_err();
+        assert!(missing.contains("missing field `expected_value`"), "{}", missing);
+    }
+
+    #[test]
+    fn test_service_running_while_disabled_is_rejected() {
+        let check = parse_check(
+            r#"{"id": "sysmain", "name": "SysMain", "check_type": "service_state", "service_name": "SysMain",
+                "service_state": "running", "start_type": "disabled"}"#,
+        )
+        .unwrap();
+        assert_eq!(check.validate(), vec!["a disabled service can't be expected to be running".to_string()]);
+    }
+
+    #[test]
+    fn test_infinite_amounts_are_rejected() {
+        let check = parse_check(
+            r#"{"id": "ram", "name": "Free RAM", "check_type": "memory_available", "expected_value": "inf"}"#,
+        )
+        .unwrap();
+        assert_eq!(check.validate(), vec!["expected_value 'inf' is not an amount of memory in GB".to_string()]);
+    }
+
+    #[test]
+    fn test_bundled_config_loads() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../config/checklist.json");
+        let config = Config::load(&path).unwrap();
+        assert!(config.validate().is_empty(), "{:?}", config.validate());
+
+        let saved = serde_json::to_value(&config.root).unwrap();
+        let check = &saved["scenarios"]["gaming_fps"]["checks"][1];
+        assert_eq!(check["check_type"], "registry_dword");
+        assert_eq!(check["expected_value"], "1");
+    }
+}
@@ -0,0 +1,79 @@
+//! Drift detection between consecutive check runs
+
+use crate::checkers::CheckResult;
+use std::collections::HashMap;
+
+/// Remembers the last pass/fail state of each check to spot settings that drift
+#[derive(Debug, Clone, Default)]
+pub struct DriftTracker {
+    previous_status: HashMap<String, bool>,
+}
+
+impl DriftTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a run and return the checks that changed from passing to failing
+    /// Checks seen for the first time count as previously passing; skipped
+    /// checks are left out
+    pub fn update<'a>(&mut self, results: &'a [CheckResult]) -> Vec<&'a CheckResult> {
+        let mut drifted = Vec::new();
+
+        for result in results.iter().filter(|r| !r.skipped) {
+            let was_passing = self.previous_status.get(&result.id).copied().unwrap_or(true);
+
+            if was_passing && !result.passed {
+                drifted.push(result);
+            }
+
+            self.previous_status.insert(result.id.clone(), result.passed);
+        }
+
+        drifted
+    }
+
+    /// Forget all previous states (e.g. after switching scenario)
+    pub fn reset(&mut self) {
+        self.previous_status.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::run_all_checks;
+    use crate::config::Config;
+    use crate::probe::{FakeProbe, RegistryHive};
+
+    #[test]
+    fn test_drift_reported_once_per_transition() {
+        let probe = FakeProbe::new()
+            .with_dword(RegistryHive::CurrentUser, "Software\\Microsoft\\GameBar", "AutoGameModeEnabled", 1);
+        let config = Config::default();
+        let checks: Vec<_> = config
+            .get_scenario_checks()
+            .unwrap()
+            .iter()
+            .filter(|c| c.id == "game_mode")
+            .cloned()
+            .collect();
+
+        let mut tracker = DriftTracker::new();
+        assert!(tracker.update(&run_all_checks(&probe, &checks)).is_empty());
+
+        probe.set_value(
+            RegistryHive::CurrentUser,
+            "Software\\Microsoft\\GameBar",
+            "AutoGameModeEnabled",
+            crate::probe::RegistryValue::Dword(0),
+        );
+        let results = run_all_checks(&probe, &checks);
+        let drifted = tracker.update(&results);
+        assert_eq!(drifted.len(), 1);
+        assert_eq!(drifted[0].id, "game_mode");
+
+        // Still failing: not reported again
+        assert!(tracker.update(&run_all_checks(&probe, &checks)).is_empty());
+    }
+}
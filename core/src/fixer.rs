@@ -0,0 +1,833 @@
+//! Fix functionality for automatically resolving failing checks
+//!
+//! This module provides the ability to automatically fix certain types of
+//! failing checks, including registry values, power plans, processes,
+//! services, scheduled tasks, display modes and HDR.
+
+use crate::checkers::expectation::Expectation;
+use crate::checkers::{display, power_plan, processes, registry};
+use crate::config::{
+    CheckConfig, CheckKind, DisplayCheck, PowerSettingCheck, ProcessCheck, RegistryCheck,
+    RegistryExistenceCheck, ServiceCheck, ValueCheck,
+};
+use crate::probe::{
+    Chassis, DisplayInfo, DisplayMode, ProcessCacheProbe, RegistryHive, RegistryValueType, ServiceState, SystemProbe,
+    KEY_NOT_FOUND, VALUE_NOT_FOUND,
+};
+use serde::{Deserialize, Serialize};
+
+/// Result of a fix attempt
+#[derive(Clone, Debug, Serialize)]
+pub struct FixResult {
+    pub check_id: String,
+    pub check_name: String,
+    pub success: bool,
+    /// Not attempted: the check needs a manual fix or doesn't apply to this machine
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+    pub message: String,
+    /// What the fix replaced, when it can be put back with [`restore`]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous: Option<PreviousState>,
+}
+
+/// State a fix replaced
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PreviousState {
+    /// A display's mode before the display mode fix switched it
+    DisplayMode { device_name: String, mode: DisplayMode },
+}
+
+/// Capability to fix a check
+#[derive(Clone, Debug, PartialEq)]
+pub enum FixCapability {
+    /// Can fix without elevation
+    Direct,
+    /// Requires admin/UAC elevation
+    RequiresAdmin,
+    /// Cannot be automatically fixed
+    Manual { reason: String },
+}
+
+impl Default for FixCapability {
+    fn default() -> Self {
+        FixCapability::Manual {
+            reason: "Not supported".to_string(),
+        }
+    }
+}
+
+/// Determine the fix capability for a check config
+pub fn get_fix_capability(config: &CheckConfig) -> FixCapability {
+    match &config.kind {
+        CheckKind::PowerScheme(_) => FixCapability::Direct,
+        CheckKind::PowerMode(_) => FixCapability::Direct,
+
+        CheckKind::RegistryDword(check)
+        | CheckKind::RegistryQword(check)
+        | CheckKind::RegistryString(check)
+        | CheckKind::RegistryExpandString(check)
+        | CheckKind::RegistryMultiString(check)
+        | CheckKind::RegistryBinary(check) => {
+            if registry::requires_admin(&check.registry_path) {
+                FixCapability::RequiresAdmin
+            } else {
+                FixCapability::Direct
+            }
+        }
+
+        CheckKind::RegistryExists(_) => FixCapability::Manual {
+            reason: "Cannot create missing registry entries".to_string(),
+        },
+
+        CheckKind::RegistryAbsent(check) => {
+            if check.registry_key.is_none() {
+                FixCapability::Manual {
+                    reason: "Deleting whole registry keys is not supported".to_string(),
+                }
+            } else if registry::parse_root_key(&check.registry_path)
+                .is_some_and(|(hive, _)| hive == RegistryHive::CurrentUser)
+            {
+                FixCapability::Direct
+            } else {
+                FixCapability::Manual {
+                    reason: "Only HKCU values are deleted automatically".to_string(),
+                }
+            }
+        }
+
+        CheckKind::ProcessAbsent(_) => FixCapability::Direct,
+
+        CheckKind::ProcessPresent(_) => FixCapability::Manual {
+            reason: "Cannot auto-start applications".to_string(),
+        },
+
+        CheckKind::DisplayResolution(_) | CheckKind::DisplayRefreshRate(_) => FixCapability::Direct,
+        CheckKind::DisplayCount(_)
+        | CheckKind::DisplayBitDepth(_)
+        | CheckKind::DisplayScaling(_)
+        | CheckKind::DisplayOrientation(_) => FixCapability::Manual {
+            reason: "Display settings must be changed in Windows Settings".to_string(),
+        },
+        CheckKind::HdrEnabled(_) => FixCapability::Direct,
+
+        CheckKind::PowerSetting(_) => FixCapability::Direct,
+        CheckKind::ServiceState(_) => FixCapability::RequiresAdmin,
+        CheckKind::TaskDisabled(_) | CheckKind::TaskNotRunning(_) => FixCapability::RequiresAdmin,
+        CheckKind::AcPower(_) | CheckKind::BatteryLevel(_) => FixCapability::Manual {
+            reason: "Plug in the charger".to_string(),
+        },
+        CheckKind::CpuIdle(_) => FixCapability::Manual {
+            reason: "Close the listed processes or add process_absent checks for them".to_string(),
+        },
+        CheckKind::MemoryAvailable(_) | CheckKind::CommitCharge(_) => FixCapability::Manual {
+            reason: "Close memory-hungry applications".to_string(),
+        },
+        CheckKind::Pagefile(_) => FixCapability::Manual {
+            reason: "Pagefile size is set in System Properties and needs a reboot".to_string(),
+        },
+        CheckKind::DiskFree(_) => FixCapability::Manual {
+            reason: "Free up space on the drive".to_string(),
+        },
+        CheckKind::DriveType(_) => FixCapability::Manual {
+            reason: "Move the files to a drive of the expected type".to_string(),
+        },
+        CheckKind::PendingReboot(_) => FixCapability::Manual {
+            reason: "Restart Windows before benchmarking".to_string(),
+        },
+        CheckKind::WindowsUpdateActive(_) => FixCapability::Manual {
+            reason: "Wait for Windows Update to finish, or pause updates".to_string(),
+        },
+    }
+}
+
+/// Attempt to fix a single check
+/// Returns FixResult with success/failure and message
+pub fn fix_check(probe: &dyn SystemProbe, config: &CheckConfig) -> FixResult {
+    let skip = |message: String| FixResult {
+        check_id: config.id.clone(),
+        check_name: config.name.clone(),
+        success: false,
+        skipped: true,
+        message,
+        previous: None,
+    };
+    if config.laptop_only && probe.chassis() == Ok(Chassis::Desktop) {
+        return skip("Laptop-only check on a desktop".to_string());
+    }
+
+    let capability = get_fix_capability(config);
+
+    match capability {
+        FixCapability::Manual { reason } => skip(format!("Cannot auto-fix: {}", reason)),
+        FixCapability::RequiresAdmin => {
+            // For now, attempt the fix directly - it will fail with access denied
+            // In the future, we could implement UAC elevation
+            attempt_fix(probe, config)
+        }
+        FixCapability::Direct => attempt_fix(probe, config),
+    }
+}
+
+/// Actually attempt to apply a fix
+fn attempt_fix(probe: &dyn SystemProbe, config: &CheckConfig) -> FixResult {
+    let mut previous = None;
+    let mut record_previous = |(message, replaced): (String, PreviousState)| {
+        previous = Some(replaced);
+        message
+    };
+
+    let result = match &config.kind {
+        CheckKind::PowerScheme(check) => fix_power_scheme(probe, check),
+        CheckKind::PowerMode(check) => fix_power_mode(probe, check),
+        CheckKind::RegistryDword(check) => fix_registry_value(probe, check, RegistryValueType::Dword),
+        CheckKind::RegistryQword(check) => fix_registry_value(probe, check, RegistryValueType::Qword),
+        CheckKind::RegistryString(check) => {
+            fix_registry_value(probe, check, RegistryValueType::String)
+        }
+        CheckKind::RegistryExpandString(check) => {
+            fix_registry_value(probe, check, RegistryValueType::ExpandString)
+        }
+        CheckKind::RegistryMultiString(check) => {
+            fix_registry_value(probe, check, RegistryValueType::MultiString)
+        }
+        CheckKind::RegistryBinary(check) => {
+            fix_registry_value(probe, check, RegistryValueType::Binary)
+        }
+        CheckKind::RegistryExists(_) => Err("Cannot create missing registry entries".to_string()),
+        CheckKind::RegistryAbsent(check) => fix_registry_absent(probe, check),
+        CheckKind::ProcessAbsent(check) => fix_process_absent(probe, check),
+        CheckKind::ProcessPresent(_) => Err("Cannot auto-start applications".to_string()),
+        CheckKind::DisplayResolution(check) => fix_resolution(probe, check).map(&mut record_previous),
+        CheckKind::DisplayRefreshRate(check) => fix_refresh_rate(probe, check).map(&mut record_previous),
+        CheckKind::DisplayCount(_)
+        | CheckKind::DisplayBitDepth(_)
+        | CheckKind::DisplayScaling(_)
+        | CheckKind::DisplayOrientation(_) => Err("Display settings cannot be auto-fixed".to_string()),
+        CheckKind::HdrEnabled(check) => fix_hdr(probe, check),
+        CheckKind::PowerSetting(check) => fix_power_setting(probe, check),
+        CheckKind::ServiceState(check) => fix_service_state(probe, check),
+        CheckKind::TaskDisabled(check) => probe
+            .set_task_enabled(&check.task_name, false)
+            .map(|()| format!("Disabled {}", check.task_name)),
+        CheckKind::TaskNotRunning(check) => probe
+            .stop_task(&check.task_name)
+            .map(|()| format!("Stopped {}", check.task_name)),
+        CheckKind::AcPower(_) | CheckKind::BatteryLevel(_) => Err("Cannot change power source".to_string()),
+        CheckKind::CpuIdle(_) => Err("Cannot reduce background CPU load".to_string()),
+        CheckKind::MemoryAvailable(_) | CheckKind::CommitCharge(_) => Err("Cannot free memory".to_string()),
+        CheckKind::Pagefile(_) => Err("Pagefile settings cannot be auto-fixed".to_string()),
+        CheckKind::DiskFree(_) | CheckKind::DriveType(_) => Err("Drive contents cannot be auto-fixed".to_string()),
+        CheckKind::PendingReboot(_) => Err("Cannot restart Windows automatically".to_string()),
+        CheckKind::WindowsUpdateActive(_) => Err("Cannot stop Windows Update activity".to_string()),
+    };
+
+    match result {
+        Ok(msg) => FixResult {
+            check_id: config.id.clone(),
+            check_name: config.name.clone(),
+            success: true,
+            skipped: false,
+            message: msg,
+            previous,
+        },
+        Err(msg) => FixResult {
+            check_id: config.id.clone(),
+            check_name: config.name.clone(),
+            success: false,
+            skipped: false,
+            message: msg,
+            previous: None,
+        },
+    }
+}
+
+/// The value a fix should set for an expected value
+/// (the value itself, the first of a one_of list, or the bound of a range)
+fn fix_target(expected: &Expectation) -> Result<String, String> {
+    expected
+        .fix_target()
+        .ok_or_else(|| format!("No single value to set for '{}'", expected))
+}
+
+/// Fix a power scheme check by setting the expected power plan
+fn fix_power_scheme(probe: &dyn SystemProbe, check: &ValueCheck) -> Result<String, String> {
+    let expected = fix_target(&check.expected_value)?;
+    power_plan::set_power_scheme(probe, &expected)
+}
+
+/// Fix a power mode check by setting the expected power mode
+fn fix_power_mode(probe: &dyn SystemProbe, check: &ValueCheck) -> Result<String, String> {
+    let expected = fix_target(&check.expected_value)?;
+    power_plan::set_power_mode(probe, &expected)?;
+    Ok(format!("Set power mode to {}", expected))
+}
+
+/// Fix a power setting check by writing the expected value index
+fn fix_power_setting(probe: &dyn SystemProbe, check: &PowerSettingCheck) -> Result<String, String> {
+    let expected = fix_target(&check.expected_value)?;
+    let value = expected
+        .parse::<u32>()
+        .map_err(|_| format!("'{}' is not a power setting value index", expected))?;
+
+    power_plan::set_power_setting(probe, check, value)?;
+    let sources: Vec<String> = power_plan::power_sources(check).iter().map(|s| s.to_string()).collect();
+    Ok(format!("Set {} to {} ({})", check.power_setting.as_str(), value, sources.join(", ")))
+}
+
+/// Switch the targeted display to the expected resolution at the highest
+/// refresh rate it offers there
+fn fix_resolution(probe: &dyn SystemProbe, check: &DisplayCheck) -> Result<(String, PreviousState), String> {
+    let resolution = fix_target(&check.expected_value)?;
+    let display = display::find_display(probe, check.display.as_ref())?;
+
+    let mode = probe
+        .display_modes(&display.device_name)?
+        .into_iter()
+        .filter(|m| format!("{}x{}", m.width, m.height) == resolution)
+        .max_by_key(|m| m.refresh_hz)
+        .ok_or_else(|| format!("{} has no {} mode", display.device_name, resolution))?;
+    switch_display_mode(probe, &display, mode)
+}
+
+/// Switch the targeted display to the highest matching refresh rate at its
+/// current resolution
+fn fix_refresh_rate(probe: &dyn SystemProbe, check: &DisplayCheck) -> Result<(String, PreviousState), String> {
+    let expected = check.expected_value.bare_as_minimum();
+    let display = display::find_display(probe, check.display.as_ref())?;
+    let current = display.mode;
+
+    let mode = probe
+        .display_modes(&display.device_name)?
+        .into_iter()
+        .filter(|m| (m.width, m.height) == (current.width, current.height))
+        .filter(|m| expected.matches(&m.refresh_hz.to_string()))
+        .max_by_key(|m| m.refresh_hz)
+        .ok_or_else(|| {
+            format!(
+                "{} has no {} mode at {}x{}",
+                display.device_name,
+                expected.describe(&|v| format!("{}Hz", v)),
+                current.width,
+                current.height
+            )
+        })?;
+    switch_display_mode(probe, &display, mode)
+}
+
+fn switch_display_mode(
+    probe: &dyn SystemProbe,
+    display: &DisplayInfo,
+    mode: DisplayMode,
+) -> Result<(String, PreviousState), String> {
+    probe.set_display_mode(&display.device_name, mode)?;
+    Ok((
+        format!("Set {} to {} (was {})", display.device_name, mode, display.mode),
+        PreviousState::DisplayMode {
+            device_name: display.device_name.clone(),
+            mode: display.mode,
+        },
+    ))
+}
+
+/// Put back a state a fix replaced
+pub fn restore(probe: &dyn SystemProbe, previous: &PreviousState) -> Result<String, String> {
+    match previous {
+        PreviousState::DisplayMode { device_name, mode } => {
+            probe.set_display_mode(device_name, *mode)?;
+            Ok(format!("Restored {} to {}", device_name, mode))
+        }
+    }
+}
+
+/// Put back every state a run of fixes replaced, newest first, so a setting
+/// changed by more than one fix ends up where it was before the first
+pub fn restore_all(probe: &dyn SystemProbe, replaced: &[PreviousState]) -> Vec<Result<String, String>> {
+    replaced.iter().rev().map(|previous| restore(probe, previous)).collect()
+}
+
+/// Turn HDR on or off on the display the check targets
+fn fix_hdr(probe: &dyn SystemProbe, check: &DisplayCheck) -> Result<String, String> {
+    let enabled = match fix_target(&check.expected_value)?.as_str() {
+        "1" => true,
+        "0" => false,
+        other => return Err(format!("'{}' is not an HDR state (use 1 or 0)", other)),
+    };
+
+    let display = display::find_display(probe, check.display.as_ref())?;
+    if enabled && !display.hdr_supported {
+        return Err(format!("{} does not support HDR", display.device_name));
+    }
+    probe.set_hdr_enabled(&display.device_name, enabled)?;
+    Ok(format!("{} HDR on {}", if enabled { "Enabled" } else { "Disabled" }, display.device_name))
+}
+
+/// Fix a registry check by writing the expected value with the expected type
+/// (this also corrects a value stored with the wrong type)
+fn fix_registry_value(
+    probe: &dyn SystemProbe,
+    check: &RegistryCheck,
+    value_type: RegistryValueType,
+) -> Result<String, String> {
+    let expected = fix_target(&check.expected_value)?;
+    let value = registry::parse_value(value_type, &expected)?;
+
+    registry::write_value(probe, &check.registry_path, check.registry_view, &check.registry_key, &value)?;
+    Ok(format!("Set {} to {} ({})", check.registry_key, registry::value_text(&value), value_type))
+}
+
+/// Fix a registry absent check by deleting the value
+fn fix_registry_absent(probe: &dyn SystemProbe, check: &RegistryExistenceCheck) -> Result<String, String> {
+    let value_name = check
+        .registry_key
+        .as_ref()
+        .ok_or("Deleting whole registry keys is not supported")?;
+
+    match registry::delete_value(probe, &check.registry_path, check.registry_view, value_name) {
+        Ok(()) => Ok(format!("Deleted {}", value_name)),
+        Err(e) if e == KEY_NOT_FOUND || e == VALUE_NOT_FOUND => {
+            Ok(format!("{} is already absent", value_name))
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Fix a process absent check by terminating the process
+fn fix_process_absent(probe: &dyn SystemProbe, check: &ProcessCheck) -> Result<String, String> {
+    let process_name = check.process_name.as_str();
+
+    let count = processes::terminate_process(probe, check)?;
+    if count > 0 {
+        Ok(format!("Terminated {} instance(s) of {}", count, process_name))
+    } else {
+        Ok(format!("{} is not running", process_name))
+    }
+}
+
+/// Fix a service check: change the start type first (so a disabled service
+/// can't be restarted in between), then start or stop the service
+fn fix_service_state(probe: &dyn SystemProbe, check: &ServiceCheck) -> Result<String, String> {
+    let name = &check.service_name;
+    let current = probe.service_status(name)?;
+    let mut changes = Vec::new();
+
+    if let Some(start_type) = check.start_type.filter(|t| *t != current.start_type) {
+        probe.set_service_start_type(name, start_type)?;
+        changes.push(format!("set {} start type to {}", name, start_type));
+    }
+
+    if let Some(state) = check.service_state.filter(|s| *s != current.state) {
+        let running = state == ServiceState::Running;
+        probe.set_service_running(name, running)?;
+        changes.push(format!("{} {}", if running { "started" } else { "stopped" }, name));
+    }
+
+    if changes.is_empty() {
+        return Ok(format!("{} is already as expected", name));
+    }
+    let message = changes.join(", ");
+    Ok(message[..1].to_uppercase() + &message[1..])
+}
+
+/// Fix all failing checks in a list
+/// Returns a summary of results
+pub fn fix_all(probe: &dyn SystemProbe, configs: &[CheckConfig], failing_ids: &[String]) -> Vec<FixResult> {
+    // One process list for every process fix in the pass
+    let probe = ProcessCacheProbe::new(probe);
+    let mut results = Vec::new();
+
+    for config in configs {
+        if failing_ids.contains(&config.id) && config.enabled {
+            let result = fix_check(&probe, config);
+            results.push(result);
+        }
+    }
+
+    results
+}
+
+/// Check if any fixes in a list require admin privileges
+pub fn any_require_admin(configs: &[CheckConfig], failing_ids: &[String]) -> bool {
+    configs.iter().any(|config| {
+        failing_ids.contains(&config.id)
+            && config.enabled
+            && get_fix_capability(config) == FixCapability::RequiresAdmin
+    })
+}
+
+/// Get counts of fixable checks by type
+pub fn get_fix_counts(configs: &[CheckConfig], failing_ids: &[String]) -> (usize, usize, usize) {
+    let mut direct = 0;
+    let mut admin = 0;
+    let mut manual = 0;
+
+    for config in configs {
+        if failing_ids.contains(&config.id) && config.enabled {
+            match get_fix_capability(config) {
+                FixCapability::Direct => direct += 1,
+                FixCapability::RequiresAdmin => admin += 1,
+                FixCapability::Manual { .. } => manual += 1,
+            }
+        }
+    }
+
+    (direct, admin, manual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::run_all_checks;
+    use crate::probe::{FakeProbe, Guid, RegistryHive, ServiceStartType};
+    use crate::report::{CheckReport, FixReport};
+
+    #[test]
+    fn test_fix_all_then_recheck() {
+        let probe = FakeProbe::new()
+            .with_dword(RegistryHive::CurrentUser, "System\\GameConfigStore", "GameDVR_Enabled", 1)
+            .with_process("C:\\Users\\bench\\AppData\\Local\\Discord\\app-1.0\\Discord.exe")
+            .with_process("C:\\Users\\bench\\AppData\\Local\\Discord\\app-1.0\\Discord.exe");
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "xbox_dvr_off", "name": "Xbox DVR Disabled", "check_type": "registry_dword",
+                    "enabled": true, "registry_path": "HKCU\\System\\GameConfigStore",
+                    "registry_key": "GameDVR_Enabled", "expected_value": "0"
+                },
+                {
+                    "id": "no_discord", "name": "Discord Not Running", "check_type": "process_absent",
+                    "enabled": true, "process_name": "discord.exe"
+                },
+                {
+                    "id": "power_plan", "name": "Power Plan", "check_type": "power_scheme",
+                    "enabled": true, "expected_value": "high_performance"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let failing: Vec<String> = run_all_checks(&probe, &checks)
+            .into_iter()
+            .filter(|r| r.failed())
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(failing.len(), 3);
+
+        let fixes = fix_all(&probe, &checks, &failing);
+        assert!(fixes.iter().all(|f| f.success));
+        assert_eq!(fixes[1].message, "Terminated 2 instance(s) of discord.exe");
+
+        assert!(run_all_checks(&probe, &checks).iter().all(|r| r.passed));
+    }
+
+    #[test]
+    fn test_registry_absent_deletes_hkcu_value() {
+        let probe = FakeProbe::new()
+            .with_dword(RegistryHive::CurrentUser, "Software\\Bench", "Overlay", 1)
+            .with_dword(RegistryHive::LocalMachine, "SOFTWARE\\Bench", "Overlay", 1);
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "user_overlay", "name": "Overlay Off", "check_type": "registry_absent",
+                    "enabled": true, "registry_path": "HKCU\\Software\\Bench", "registry_key": "Overlay"
+                },
+                {
+                    "id": "machine_overlay", "name": "Machine Overlay Off", "check_type": "registry_absent",
+                    "enabled": true, "registry_path": "HKLM\\SOFTWARE\\Bench", "registry_key": "Overlay"
+                },
+                {
+                    "id": "bench_key", "name": "Bench Key", "check_type": "registry_exists",
+                    "enabled": true, "registry_path": "HKCU\\Software\\Bench"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let results = run_all_checks(&probe, &checks);
+        assert_eq!(results[0].current_value, "Present");
+        assert!(!results[0].passed);
+        assert!(results[2].passed);
+        assert!(matches!(get_fix_capability(&checks[1]), FixCapability::Manual { .. }));
+
+        let fix = attempt_fix(&probe, &checks[0]);
+        assert!(fix.success);
+        assert_eq!(fix.message, "Deleted Overlay");
+
+        let results = run_all_checks(&probe, &checks);
+        assert!(results[0].passed);
+        assert!(!results[1].passed);
+        // The key is still there after its only value is deleted
+        assert!(results[2].passed);
+    }
+
+    #[test]
+    fn test_hkcu_redirected_to_interactive_user() {
+        let sid = "S-1-5-21-1000-2000-3000-1001";
+        let probe = FakeProbe::new()
+            .with_interactive_user(sid)
+            .with_dword(RegistryHive::CurrentUser, "Software\\Microsoft\\GameBar", "AutoGameModeEnabled", 1)
+            .with_dword(RegistryHive::Users, &format!("{}\\Software\\Microsoft\\GameBar", sid), "AutoGameModeEnabled", 0);
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[{
+                "id": "game_mode", "name": "Game Mode", "check_type": "registry_dword", "enabled": true,
+                "registry_path": "HKCU\\Software\\Microsoft\\GameBar",
+                "registry_key": "AutoGameModeEnabled", "expected_value": "1"
+            }]"#,
+        )
+        .unwrap();
+
+        let result = &run_all_checks(&probe, &checks)[0];
+        assert!(!result.passed);
+        assert_eq!(result.registry_hive.as_deref(), Some("HKU\\S-1-5-21-1000-2000-3000-1001"));
+
+        assert!(attempt_fix(&probe, &checks[0]).success);
+        assert!(run_all_checks(&probe, &checks)[0].passed);
+    }
+
+    #[test]
+    fn test_service_disabled_then_stopped() {
+        let probe = FakeProbe::new()
+            .with_service("SysMain", ServiceState::Running, ServiceStartType::Automatic)
+            .with_service("DiagTrack", ServiceState::Stopped, ServiceStartType::Disabled);
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "sysmain_off", "name": "SysMain Disabled", "check_type": "service_state",
+                    "enabled": true, "service_name": "SysMain", "service_state": "stopped",
+                    "start_type": "disabled"
+                },
+                {
+                    "id": "telemetry_off", "name": "Telemetry Disabled", "check_type": "service_state",
+                    "enabled": true, "service_name": "DiagTrack", "start_type": "disabled"
+                },
+                {
+                    "id": "no_vendor_service", "name": "Vendor Service Stopped", "check_type": "service_state",
+                    "enabled": true, "service_name": "VendorUpdater", "service_state": "stopped"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let results = run_all_checks(&probe, &checks);
+        assert_eq!(results[0].current_value, "Running, Automatic");
+        assert_eq!(results[0].expected_value, "Stopped, Disabled");
+        assert!(!results[0].passed);
+        assert!(results[1].passed);
+        assert_eq!(results[2].current_value, "Not installed");
+        assert!(results[2].passed);
+        assert!(matches!(get_fix_capability(&checks[0]), FixCapability::RequiresAdmin));
+
+        let fix = attempt_fix(&probe, &checks[0]);
+        assert!(fix.success);
+        assert_eq!(fix.message, "Set SysMain start type to Disabled, stopped SysMain");
+        assert!(run_all_checks(&probe, &checks).iter().all(|r| r.passed));
+    }
+
+    #[test]
+    fn test_scheduled_task_disabled_and_stopped() {
+        let probe = FakeProbe::new().with_task("\\Microsoft\\Windows\\Defrag\\ScheduledDefrag", true, true);
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "defrag_off", "name": "Defrag Disabled", "check_type": "task_disabled",
+                    "enabled": true, "task_name": "\\Microsoft\\Windows\\Defrag\\ScheduledDefrag"
+                },
+                {
+                    "id": "defrag_idle", "name": "Defrag Not Running", "check_type": "task_not_running",
+                    "enabled": true, "task_name": "\\microsoft\\windows\\defrag\\scheduleddefrag"
+                },
+                {
+                    "id": "vendor_updater", "name": "Vendor Updater Disabled", "check_type": "task_disabled",
+                    "enabled": true, "task_name": "VendorUpdateTask"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let results = run_all_checks(&probe, &checks);
+        assert_eq!(results[0].current_value, "Enabled");
+        assert_eq!(results[1].current_value, "Running");
+        assert!(!results[0].passed && !results[1].passed);
+        assert_eq!(results[2].current_value, "Not found");
+        assert!(results[2].passed);
+
+        let failing = vec!["defrag_off".to_string(), "defrag_idle".to_string()];
+        assert!(fix_all(&probe, &checks, &failing).iter().all(|f| f.success));
+        assert!(run_all_checks(&probe, &checks).iter().all(|r| r.passed));
+    }
+
+    #[test]
+    fn test_power_setting_fix_writes_ac_and_dc() {
+        let processor = Guid::from_u128(0x54533251_82be_4824_96c1_47b60b740d00);
+        let min_state = Guid::from_u128(0x893dee8e_2bef_41e0_89c6_b55d0929964c);
+        let probe = FakeProbe::new();
+        let scheme = probe.active_power_scheme().unwrap();
+        let probe = probe.with_power_setting(scheme, processor, min_state, 5, 5);
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "min_state", "name": "Min Processor State 100%", "check_type": "power_setting",
+                    "enabled": true, "power_setting": "min_processor_state", "expected_value": ">=100"
+                },
+                {
+                    "id": "min_state_ac", "name": "Min Processor State (AC)", "check_type": "power_setting",
+                    "enabled": true, "power_setting": "54533251-82be-4824-96c1-47b60b740d00/893dee8e-2bef-41e0-89c6-b55d0929964c",
+                    "expected_value": "100", "power_source": "ac"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let results = run_all_checks(&probe, &checks);
+        assert_eq!(results[0].current_value, "AC 5, DC 5");
+        assert_eq!(results[1].current_value, "5");
+        assert!(results.iter().all(|r| !r.passed));
+
+        let fix = attempt_fix(&probe, &checks[0]);
+        assert_eq!(fix.message, "Set min_processor_state to 100 (AC, DC)");
+        assert!(run_all_checks(&probe, &checks).iter().all(|r| r.passed));
+    }
+
+    #[test]
+    fn test_hdr_toggled_on_targeted_display() {
+        let probe = FakeProbe::new()
+            .with_monitor("\\\\.\\DISPLAY2", "LG ULTRAGEAR", 2560, 1440, 144)
+            .with_hdr("\\\\.\\DISPLAY2", false);
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "hdr_on", "name": "HDR On", "check_type": "hdr_enabled",
+                    "enabled": true, "expected_value": "1", "display": "LG ULTRAGEAR"
+                },
+                {
+                    "id": "hdr_primary", "name": "HDR On (primary)", "check_type": "hdr_enabled",
+                    "enabled": true, "expected_value": "1"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let results = run_all_checks(&probe, &checks);
+        assert_eq!(results[0].current_value, "Disabled (\\\\.\\DISPLAY2 LG ULTRAGEAR)");
+        assert_eq!(results[1].current_value, "Not supported (\\\\.\\DISPLAY1 Generic PnP Monitor)");
+
+        let fix = attempt_fix(&probe, &checks[0]);
+        assert!(fix.success, "{}", fix.message);
+        assert_eq!(fix.message, "Enabled HDR on \\\\.\\DISPLAY2");
+        assert!(!attempt_fix(&probe, &checks[1]).success);
+
+        let results = run_all_checks(&probe, &checks);
+        assert!(results[0].passed);
+        assert!(!results[1].passed);
+    }
+
+    #[test]
+    fn test_fix_report_tags_failed_and_skipped_fixes() {
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "hdr_on", "name": "HDR On", "check_type": "hdr_enabled",
+                    "enabled": true, "expected_value": "1"
+                },
+                {
+                    "id": "no_reboot", "name": "No Pending Reboot", "check_type": "pending_reboot",
+                    "enabled": true, "expected_value": "1"
+                }
+            ]"#,
+        )
+        .unwrap();
+        let probe = FakeProbe::new();
+        let failing: Vec<String> = checks.iter().map(|c| c.id.clone()).collect();
+
+        let report = FixReport {
+            fixes: fix_all(&probe, &checks, &failing),
+            after: CheckReport::new("default", "Default", run_all_checks(&probe, &checks)),
+        };
+        let text = report.to_text();
+        assert!(text.contains("[FAIL ] HDR On: \\\\.\\DISPLAY1 does not support HDR"), "{}", text);
+        assert!(text.contains("[SKIP ] No Pending Reboot: Cannot auto-fix: "), "{}", text);
+    }
+
+    #[test]
+    fn test_display_mode_fix_and_restore() {
+        let probe = FakeProbe::new().with_display(1920, 1080, 60).with_display_modes(
+            "\\\\.\\DISPLAY1",
+            &[(1920, 1080, 60), (1920, 1080, 144), (2560, 1440, 60), (2560, 1440, 120), (2560, 1440, 165)],
+        );
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "res", "name": "1440p", "check_type": "display_resolution",
+                    "enabled": true, "expected_value": "2560x1440"
+                },
+                {
+                    "id": "refresh", "name": "120-144Hz", "check_type": "display_refresh_rate",
+                    "enabled": true, "expected_value": "120..=144"
+                },
+                {
+                    "id": "4k", "name": "4K", "check_type": "display_resolution",
+                    "enabled": true, "expected_value": "3840x2160"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let fix = attempt_fix(&probe, &checks[0]);
+        assert!(fix.success, "{}", fix.message);
+        assert_eq!(fix.message, "Set \\\\.\\DISPLAY1 to 2560x1440 @ 165Hz (was 1920x1080 @ 60Hz)");
+        let original = fix.previous.unwrap();
+
+        let fix = attempt_fix(&probe, &checks[1]);
+        assert_eq!(fix.message, "Set \\\\.\\DISPLAY1 to 2560x1440 @ 120Hz (was 2560x1440 @ 165Hz)");
+        assert!(!attempt_fix(&probe, &checks[2]).success);
+
+        let results = run_all_checks(&probe, &checks);
+        assert!(results[0].passed && results[1].passed);
+
+        assert_eq!(restore(&probe, &original).unwrap(), "Restored \\\\.\\DISPLAY1 to 1920x1080 @ 60Hz");
+        assert!(!run_all_checks(&probe, &checks)[0].passed);
+    }
+
+    #[test]
+    fn test_restore_all_after_two_fixes_on_one_display() {
+        let probe = FakeProbe::new().with_display(1920, 1080, 60).with_display_modes(
+            "\\\\.\\DISPLAY1",
+            &[(1920, 1080, 60), (2560, 1440, 60), (2560, 1440, 144)],
+        );
+
+        let checks: Vec<CheckConfig> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "res", "name": "1440p", "check_type": "display_resolution",
+                    "enabled": true, "expected_value": "2560x1440"
+                },
+                {
+                    "id": "refresh", "name": "60Hz", "check_type": "display_refresh_rate",
+                    "enabled": true, "expected_value": "60"
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let failing = vec!["res".to_string(), "refresh".to_string()];
+        let fixes = fix_all(&probe, &checks, &failing);
+        assert!(fixes.iter().all(|f| f.success));
+        assert!(run_all_checks(&probe, &checks).iter().all(|r| r.passed));
+
+        let replaced: Vec<PreviousState> = fixes.into_iter().filter_map(|f| f.previous).collect();
+        assert_eq!(replaced.len(), 2);
+        assert!(restore_all(&probe, &replaced).iter().all(|r| r.is_ok()));
+
+        let mode = probe.displays().unwrap()[0].mode;
+        assert_eq!(mode, DisplayMode { width: 1920, height: 1080, refresh_hz: 60 });
+    }
+}
@@ -0,0 +1,14 @@
+//! Core of Bench Checklist
+//!
+//! Configuration parsing, the check model and its evaluation, the check
+//! library, fix planning and reporting. Everything here builds on any OS;
+//! checks reach the system only through a [`probe::SystemProbe`], and the
+//! live Win32 backend is compiled on Windows only.
+
+pub mod check_library;
+pub mod checkers;
+pub mod config;
+pub mod drift;
+pub mod fixer;
+pub mod probe;
+pub mod report;
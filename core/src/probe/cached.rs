@@ -0,0 +1,212 @@
+//! Per-pass process list sharing
+//!
+//! Listing processes is by far the most expensive probe call, and a scenario
+//! can have many process checks. [`ProcessCacheProbe`] wraps a probe for a
+//! single pass over the checks (or fixes): the first `running_processes`
+//! call is forwarded, and every later call reuses that list. Image paths are
+//! looked up once per process too. Terminated processes are dropped from the
+//! cached list so re-checks within the same pass see them gone.
+
+use crate::probe::{
+    Chassis, CpuUsage, DiskSpace, DisplayInfo, DisplayMode, DriveKind, Guid, MemoryStatus, PowerSchemeInfo,
+    PowerSettingValues, PowerSource, PowerStatus, ProcessInfo, RegistryHive, RegistryValue, RegistryView,
+    ServiceStartType, ServiceStatus, SystemProbe, TaskStatus,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Probe wrapper that lists processes at most once
+pub struct ProcessCacheProbe<'a> {
+    inner: &'a dyn SystemProbe,
+    processes: Mutex<Option<Result<Vec<ProcessInfo>, String>>>,
+    paths: Mutex<HashMap<u32, Result<String, String>>>,
+}
+
+impl<'a> ProcessCacheProbe<'a> {
+    pub fn new(inner: &'a dyn SystemProbe) -> Self {
+        Self {
+            inner,
+            processes: Mutex::new(None),
+            paths: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl SystemProbe for ProcessCacheProbe<'_> {
+    fn read_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<RegistryValue, String> {
+        self.inner.read_value(hive, view, subkey, value_name)
+    }
+
+    fn write_value(
+        &self,
+        hive: RegistryHive,
+        view: RegistryView,
+        subkey: &str,
+        value_name: &str,
+        value: &RegistryValue,
+    ) -> Result<(), String> {
+        self.inner.write_value(hive, view, subkey, value_name, value)
+    }
+
+    fn delete_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<(), String> {
+        self.inner.delete_value(hive, view, subkey, value_name)
+    }
+
+    fn interactive_user_sid(&self) -> Option<String> {
+        self.inner.interactive_user_sid()
+    }
+
+    fn active_power_scheme(&self) -> Result<Guid, String> {
+        self.inner.active_power_scheme()
+    }
+
+    fn set_active_power_scheme(&self, scheme: Guid) -> Result<(), String> {
+        self.inner.set_active_power_scheme(scheme)
+    }
+
+    fn power_schemes(&self) -> Result<Vec<PowerSchemeInfo>, String> {
+        self.inner.power_schemes()
+    }
+
+    fn duplicate_power_scheme(&self, source: Guid, copy: Guid) -> Result<(), String> {
+        self.inner.duplicate_power_scheme(source, copy)
+    }
+
+    fn power_mode(&self) -> Result<Guid, String> {
+        self.inner.power_mode()
+    }
+
+    fn set_power_mode(&self, mode: Guid) -> Result<(), String> {
+        self.inner.set_power_mode(mode)
+    }
+
+    fn power_setting(&self, scheme: Guid, subgroup: Guid, setting: Guid) -> Result<PowerSettingValues, String> {
+        self.inner.power_setting(scheme, subgroup, setting)
+    }
+
+    fn set_power_setting(
+        &self,
+        scheme: Guid,
+        subgroup: Guid,
+        setting: Guid,
+        source: PowerSource,
+        value: u32,
+    ) -> Result<(), String> {
+        self.inner.set_power_setting(scheme, subgroup, setting, source, value)
+    }
+
+    fn running_processes(&self) -> Result<Vec<ProcessInfo>, String> {
+        self.processes
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| self.inner.running_processes())
+            .clone()
+    }
+
+    fn process_path(&self, pid: u32) -> Result<String, String> {
+        self.paths
+            .lock()
+            .unwrap()
+            .entry(pid)
+            .or_insert_with(|| self.inner.process_path(pid))
+            .clone()
+    }
+
+    fn terminate_process(&self, pid: u32) -> Result<(), String> {
+        self.inner.terminate_process(pid)?;
+
+        if let Some(Ok(processes)) = self.processes.lock().unwrap().as_mut() {
+            processes.retain(|p| p.pid != pid);
+        }
+        self.paths.lock().unwrap().remove(&pid);
+        Ok(())
+    }
+
+    fn cpu_usage(&self, window: Duration) -> Result<CpuUsage, String> {
+        self.inner.cpu_usage(window)
+    }
+
+    fn memory_status(&self) -> Result<MemoryStatus, String> {
+        self.inner.memory_status()
+    }
+
+    fn disk_space(&self, path: &str) -> Result<DiskSpace, String> {
+        self.inner.disk_space(path)
+    }
+
+    fn drive_kind(&self, path: &str) -> Result<DriveKind, String> {
+        self.inner.drive_kind(path)
+    }
+
+    fn displays(&self) -> Result<Vec<DisplayInfo>, String> {
+        self.inner.displays()
+    }
+
+    fn set_hdr_enabled(&self, device_name: &str, enabled: bool) -> Result<(), String> {
+        self.inner.set_hdr_enabled(device_name, enabled)
+    }
+
+    fn display_modes(&self, device_name: &str) -> Result<Vec<DisplayMode>, String> {
+        self.inner.display_modes(device_name)
+    }
+
+    fn set_display_mode(&self, device_name: &str, mode: DisplayMode) -> Result<(), String> {
+        self.inner.set_display_mode(device_name, mode)
+    }
+
+    fn service_status(&self, name: &str) -> Result<ServiceStatus, String> {
+        self.inner.service_status(name)
+    }
+
+    fn set_service_running(&self, name: &str, running: bool) -> Result<(), String> {
+        self.inner.set_service_running(name, running)
+    }
+
+    fn set_service_start_type(&self, name: &str, start_type: ServiceStartType) -> Result<(), String> {
+        self.inner.set_service_start_type(name, start_type)
+    }
+
+    fn scheduled_task(&self, path: &str) -> Result<TaskStatus, String> {
+        self.inner.scheduled_task(path)
+    }
+
+    fn set_task_enabled(&self, path: &str, enabled: bool) -> Result<(), String> {
+        self.inner.set_task_enabled(path, enabled)
+    }
+
+    fn stop_task(&self, path: &str) -> Result<(), String> {
+        self.inner.stop_task(path)
+    }
+
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        self.inner.power_status()
+    }
+
+    fn chassis(&self) -> Result<Chassis, String> {
+        self.inner.chassis()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::probe::FakeProbe;
+
+    #[test]
+    fn test_processes_listed_once_per_pass() {
+        let fake = FakeProbe::new().with_process("C:\\Apps\\Discord.exe");
+        let probe = ProcessCacheProbe::new(&fake);
+
+        let first = probe.running_processes().unwrap();
+        assert_eq!(first.len(), 1);
+
+        // Started after the pass took its list: not seen until the next pass
+        fake.start_process("C:\\Apps\\Spotify.exe");
+        assert_eq!(probe.running_processes().unwrap(), first);
+
+        probe.terminate_process(first[0].pid).unwrap();
+        assert!(probe.running_processes().unwrap().is_empty());
+        assert_eq!(ProcessCacheProbe::new(&fake).running_processes().unwrap().len(), 1);
+    }
+}
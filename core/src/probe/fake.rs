@@ -0,0 +1,763 @@
+//! In-memory backend for [`SystemProbe`]
+//!
+//! Holds a scripted registry tree, process list, power schemes, displays,
+//! services and scheduled tasks. Writes and terminations made by the fixer
+//! mutate this state, so a test can run checks, apply fixes and run checks
+//! again.
+
+use crate::probe::{
+    Chassis, CpuUsage, DiskSpace, DisplayInfo, DisplayMode, DisplayOrientation, DriveKind, Guid, MemoryStatus,
+    PowerSchemeInfo, PowerSettingValues, PowerSource, PowerStatus, ProcessCpu, ProcessInfo, RegistryHive,
+    RegistryValue, RegistryView, ServiceStartType, ServiceState, ServiceStatus, SystemProbe, TaskStatus,
+    CPU_NOT_SAMPLED, KEY_NOT_FOUND, POWER_SETTING_NOT_FOUND, SERVICE_NOT_FOUND, TASK_NOT_FOUND, VALUE_NOT_FOUND,
+};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+const GB: u64 = 1 << 30;
+
+/// Balanced power scheme, the Windows default
+const DEFAULT_POWER_SCHEME: Guid = Guid::from_u128(0x381b4222_f694_41f0_9685_ff5bb260df2e);
+
+/// Schemes a fresh Windows install lists
+const DEFAULT_POWER_SCHEMES: &[(u128, &str)] = &[
+    (0x381b4222_f694_41f0_9685_ff5bb260df2e, "Balanced"),
+    (0x8c5e7fda_e8bf_4a96_9a85_a6e23a8c635c, "High performance"),
+    (0xa1841308_3541_4fab_bc81_f71556f20b4a, "Power saver"),
+];
+
+/// Hidden built-in template that can only be activated once duplicated
+const ULTIMATE_PERFORMANCE: (u128, &str) = (0xe9a42b02_d5df_448d_aa00_03f14749eb61, "Ultimate Performance");
+
+/// Complete state of a fake system
+#[derive(Debug, Clone)]
+pub struct FakeState {
+    /// Registry keys by (hive, lowercase subkey), each mapping lowercase value names to data
+    pub registry: HashMap<(RegistryHive, String), HashMap<String, RegistryValue>>,
+    pub power_scheme: Guid,
+    /// Installed (listed and activatable) power schemes
+    pub power_schemes: Vec<PowerSchemeInfo>,
+    /// `None` simulates a Windows build without the power mode overlay API
+    pub power_mode: Option<Guid>,
+    /// Power setting values by (scheme, subgroup, setting)
+    pub power_settings: HashMap<(Guid, Guid, Guid), PowerSettingValues>,
+    pub processes: Vec<ProcessInfo>,
+    /// Image path of each running process by PID
+    pub process_paths: HashMap<u32, String>,
+    /// Share of total CPU time each process uses, by PID; the system is as
+    /// busy as its running processes add up to
+    pub cpu_load: HashMap<u32, f64>,
+    /// How long CPU load has been sampled; `None` means long enough for any window
+    pub cpu_sampled_for: Option<Duration>,
+    pub memory: MemoryStatus,
+    /// Volumes by drive letter root (`C:\`)
+    pub volumes: Vec<(DiskSpace, DriveKind)>,
+    /// Active displays; the primary one comes first
+    pub displays: Vec<DisplayInfo>,
+    /// Modes the driver offers, by device name; a display without an entry
+    /// only offers its current mode
+    pub display_modes: HashMap<String, Vec<DisplayMode>>,
+    /// Simulates running elevated as another account than the logged-on user
+    pub interactive_user_sid: Option<String>,
+    /// Installed services by lowercase service name
+    pub services: HashMap<String, ServiceStatus>,
+    /// Scheduled tasks by lowercase task path
+    pub tasks: HashMap<String, TaskStatus>,
+    pub power_status: PowerStatus,
+    pub chassis: Chassis,
+}
+
+impl Default for FakeState {
+    fn default() -> Self {
+        Self {
+            registry: HashMap::new(),
+            power_scheme: DEFAULT_POWER_SCHEME,
+            power_schemes: DEFAULT_POWER_SCHEMES
+                .iter()
+                .map(|&(guid, name)| PowerSchemeInfo {
+                    guid: Guid::from_u128(guid),
+                    name: name.to_string(),
+                })
+                .collect(),
+            power_mode: Some(Guid::default()),
+            power_settings: HashMap::new(),
+            processes: Vec::new(),
+            process_paths: HashMap::new(),
+            cpu_load: HashMap::new(),
+            cpu_sampled_for: None,
+            memory: MemoryStatus {
+                total_bytes: 32 * GB,
+                available_bytes: 24 * GB,
+                commit_bytes: 12 * GB,
+                commit_limit_bytes: 36 * GB,
+            },
+            volumes: vec![(
+                DiskSpace {
+                    volume: "C:\\".to_string(),
+                    free_bytes: 400 * GB,
+                    total_bytes: 1000 * GB,
+                },
+                DriveKind::Nvme,
+            )],
+            displays: vec![DisplayInfo {
+                device_name: "\\\\.\\DISPLAY1".to_string(),
+                model: "Generic PnP Monitor".to_string(),
+                primary: true,
+                mode: DisplayMode {
+                    width: 1920,
+                    height: 1080,
+                    refresh_hz: 60,
+                },
+                bits_per_channel: 8,
+                scale_percent: 100,
+                orientation: DisplayOrientation::Landscape,
+                hdr_supported: false,
+                hdr_enabled: false,
+            }],
+            display_modes: HashMap::new(),
+            interactive_user_sid: None,
+            services: HashMap::new(),
+            tasks: HashMap::new(),
+            power_status: PowerStatus {
+                ac_online: Some(true),
+                battery_percent: None,
+            },
+            chassis: Chassis::Desktop,
+        }
+    }
+}
+
+/// Scriptable in-memory system
+#[derive(Debug, Default)]
+pub struct FakeProbe {
+    state: Mutex<FakeState>,
+}
+
+/// Drive letter root of a path (`d:\games` -> `D:\`)
+fn volume_root(path: &str) -> String {
+    format!("{}:\\", path.chars().next().unwrap_or_default().to_ascii_uppercase())
+}
+
+/// Task paths are case-insensitive; a bare name is in the root folder
+fn task_id(path: &str) -> String {
+    format!("\\{}", path.trim_start_matches('\\').to_lowercase())
+}
+
+/// Registry keys are case-insensitive and tolerate a trailing separator
+fn key_id(hive: RegistryHive, subkey: &str) -> (RegistryHive, String) {
+    (hive, subkey.trim_end_matches('\\').to_lowercase())
+}
+
+/// Key a read or write lands in: the 32-bit view of `HKLM\SOFTWARE` is
+/// redirected to `SOFTWARE\WOW6432Node` as on 64-bit Windows
+fn view_key_id(hive: RegistryHive, view: RegistryView, subkey: &str) -> (RegistryHive, String) {
+    let (hive, key) = key_id(hive, subkey);
+    if view != RegistryView::Bit32 || hive != RegistryHive::LocalMachine {
+        return (hive, key);
+    }
+    match key.strip_prefix("software\\") {
+        Some(rest) if !rest.starts_with("wow6432node") => (hive, format!("software\\wow6432node\\{}", rest)),
+        _ => (hive, key),
+    }
+}
+
+impl FakeProbe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a probe from a complete state
+    pub fn from_state(state: FakeState) -> Self {
+        Self {
+            state: Mutex::new(state),
+        }
+    }
+
+    /// Get a copy of the current state
+    pub fn state(&self) -> FakeState {
+        self.state.lock().unwrap().clone()
+    }
+
+    /// Builder: add a registry key with no values
+    pub fn with_key(self, hive: RegistryHive, subkey: &str) -> Self {
+        self.create_key(hive, subkey);
+        self
+    }
+
+    /// Builder: set a DWORD value (creating the key)
+    pub fn with_dword(self, hive: RegistryHive, subkey: &str, value_name: &str, data: u32) -> Self {
+        self.set_value(hive, subkey, value_name, RegistryValue::Dword(data));
+        self
+    }
+
+    /// Builder: set a string value (creating the key)
+    pub fn with_string(self, hive: RegistryHive, subkey: &str, value_name: &str, data: &str) -> Self {
+        self.set_value(hive, subkey, value_name, RegistryValue::String(data.to_string()));
+        self
+    }
+
+    /// Builder: set a value of any type (creating the key)
+    pub fn with_value(self, hive: RegistryHive, subkey: &str, value_name: &str, value: RegistryValue) -> Self {
+        self.set_value(hive, subkey, value_name, value);
+        self
+    }
+
+    /// Builder: simulate a probe that has only sampled CPU load for `seconds`
+    pub fn with_cpu_sampled_for(self, seconds: u64) -> Self {
+        self.state.lock().unwrap().cpu_sampled_for = Some(Duration::from_secs(seconds));
+        self
+    }
+
+    /// Builder: add a running process by full image path
+    pub fn with_process(self, path: &str) -> Self {
+        self.start_process(path);
+        self
+    }
+
+    /// Builder: start a process that keeps `percent` of total CPU time busy
+    pub fn with_cpu_load(self, path: &str, percent: f64) -> Self {
+        let pid = self.start_process(path);
+        self.state.lock().unwrap().cpu_load.insert(pid, percent);
+        self
+    }
+
+    /// Builder: set the active power scheme
+    pub fn with_power_scheme(self, scheme: Guid) -> Self {
+        self.state.lock().unwrap().power_scheme = scheme;
+        self
+    }
+
+    /// Builder: set the active power mode overlay (`None` = API unavailable)
+    pub fn with_power_mode(self, mode: Option<Guid>) -> Self {
+        self.state.lock().unwrap().power_mode = mode;
+        self
+    }
+
+    /// Builder: set the primary display mode
+    pub fn with_display(self, width: u32, height: u32, refresh_hz: u32) -> Self {
+        self.set_display(DisplayMode {
+            width,
+            height,
+            refresh_hz,
+        });
+        self
+    }
+
+    /// Builder: attach another (non-primary) display
+    pub fn with_monitor(self, device_name: &str, model: &str, width: u32, height: u32, refresh_hz: u32) -> Self {
+        self.state.lock().unwrap().displays.push(DisplayInfo {
+            device_name: device_name.to_string(),
+            model: model.to_string(),
+            primary: false,
+            mode: DisplayMode {
+                width,
+                height,
+                refresh_hz,
+            },
+            bits_per_channel: 8,
+            scale_percent: 100,
+            orientation: DisplayOrientation::Landscape,
+            hdr_supported: false,
+            hdr_enabled: false,
+        });
+        self
+    }
+
+    /// Builder: set the modes a display offers, as (width, height, refresh_hz)
+    pub fn with_display_modes(self, device_name: &str, modes: &[(u32, u32, u32)]) -> Self {
+        let modes = modes
+            .iter()
+            .map(|&(width, height, refresh_hz)| DisplayMode {
+                width,
+                height,
+                refresh_hz,
+            })
+            .collect();
+        self.state.lock().unwrap().display_modes.insert(device_name.to_string(), modes);
+        self
+    }
+
+    /// Builder: make a display HDR-capable, with HDR on or off
+    pub fn with_hdr(self, device_name: &str, enabled: bool) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            let display = state
+                .displays
+                .iter_mut()
+                .find(|d| d.device_name == device_name)
+                .expect("with_hdr on an unknown display");
+            display.hdr_supported = true;
+            display.hdr_enabled = enabled;
+        }
+        self
+    }
+
+    /// Builder: set a display's color depth, scaling and rotation
+    pub fn with_display_settings(
+        self,
+        device_name: &str,
+        bits_per_channel: u32,
+        scale_percent: u32,
+        orientation: DisplayOrientation,
+    ) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            let display = state
+                .displays
+                .iter_mut()
+                .find(|d| d.device_name == device_name)
+                .expect("with_display_settings on an unknown display");
+            display.bits_per_channel = bits_per_channel;
+            display.scale_percent = scale_percent;
+            display.orientation = orientation;
+        }
+        self
+    }
+
+    /// Builder: run "elevated" as another account, redirecting HKCU to `HKU\<sid>`
+    pub fn with_interactive_user(self, sid: &str) -> Self {
+        self.state.lock().unwrap().interactive_user_sid = Some(sid.to_string());
+        self
+    }
+
+    /// Builder: install a service
+    pub fn with_service(self, name: &str, state: ServiceState, start_type: ServiceStartType) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .services
+            .insert(name.to_lowercase(), ServiceStatus { state, start_type });
+        self
+    }
+
+    /// Builder: install a power scheme (e.g. an OEM plan)
+    pub fn with_installed_scheme(self, guid: Guid, name: &str) -> Self {
+        self.state.lock().unwrap().power_schemes.push(PowerSchemeInfo {
+            guid,
+            name: name.to_string(),
+        });
+        self
+    }
+
+    /// Builder: set a power setting's AC and DC values in a scheme
+    pub fn with_power_setting(self, scheme: Guid, subgroup: Guid, setting: Guid, ac: u32, dc: u32) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .power_settings
+            .insert((scheme, subgroup, setting), PowerSettingValues { ac, dc });
+        self
+    }
+
+    /// Builder: register a scheduled task
+    pub fn with_task(self, path: &str, enabled: bool, running: bool) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .tasks
+            .insert(task_id(path), TaskStatus { enabled, running });
+        self
+    }
+
+    /// Builder: set available physical memory and commit charge, in GB
+    /// (total RAM stays 32 GB)
+    pub fn with_memory(self, available_gb: u64, commit_gb: u64, commit_limit_gb: u64) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.memory.available_bytes = available_gb * GB;
+            state.memory.commit_bytes = commit_gb * GB;
+            state.memory.commit_limit_bytes = commit_limit_gb * GB;
+        }
+        self
+    }
+
+    /// Builder: add a volume (or replace one) by drive letter, e.g. "D:"
+    pub fn with_volume(self, drive: &str, free_gb: u64, total_gb: u64, kind: DriveKind) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            let volume = volume_root(drive);
+            state.volumes.retain(|(space, _)| space.volume != volume);
+            state.volumes.push((
+                DiskSpace {
+                    volume,
+                    free_bytes: free_gb * GB,
+                    total_bytes: total_gb * GB,
+                },
+                kind,
+            ));
+        }
+        self
+    }
+
+    /// Builder: make this a laptop with a battery, plugged in or not
+    pub fn with_battery(self, ac_online: bool, percent: u8) -> Self {
+        {
+            let mut state = self.state.lock().unwrap();
+            state.chassis = Chassis::Laptop;
+            state.power_status = PowerStatus {
+                ac_online: Some(ac_online),
+                battery_percent: Some(percent),
+            };
+        }
+        self
+    }
+
+    /// Create a registry key if it does not exist
+    pub fn create_key(&self, hive: RegistryHive, subkey: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .registry
+            .entry(key_id(hive, subkey))
+            .or_default();
+    }
+
+    /// Set a registry value, creating the key if needed
+    pub fn set_value(&self, hive: RegistryHive, subkey: &str, value_name: &str, value: RegistryValue) {
+        self.state
+            .lock()
+            .unwrap()
+            .registry
+            .entry(key_id(hive, subkey))
+            .or_default()
+            .insert(value_name.to_lowercase(), value);
+    }
+
+    /// Remove a registry value
+    pub fn remove_value(&self, hive: RegistryHive, subkey: &str, value_name: &str) {
+        if let Some(values) = self.state.lock().unwrap().registry.get_mut(&key_id(hive, subkey)) {
+            values.remove(&value_name.to_lowercase());
+        }
+    }
+
+    /// Start a process by full image path, returning its PID
+    pub fn start_process(&self, path: &str) -> u32 {
+        let mut state = self.state.lock().unwrap();
+        let pid = state.processes.iter().map(|p| p.pid).max().unwrap_or(1000) + 4;
+        let name = path.rsplit('\\').next().unwrap_or(path).to_string();
+        state.processes.push(ProcessInfo { pid, name });
+        state.process_paths.insert(pid, path.to_string());
+        pid
+    }
+
+    /// Set the primary display mode
+    pub fn set_display(&self, mode: DisplayMode) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(primary) = state.displays.iter_mut().find(|d| d.primary) {
+            primary.mode = mode;
+        }
+    }
+}
+
+impl SystemProbe for FakeProbe {
+    fn read_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<RegistryValue, String> {
+        let state = self.state.lock().unwrap();
+        let values = state
+            .registry
+            .get(&view_key_id(hive, view, subkey))
+            .ok_or_else(|| KEY_NOT_FOUND.to_string())?;
+        values
+            .get(&value_name.to_lowercase())
+            .cloned()
+            .ok_or_else(|| VALUE_NOT_FOUND.to_string())
+    }
+
+    fn write_value(
+        &self,
+        hive: RegistryHive,
+        view: RegistryView,
+        subkey: &str,
+        value_name: &str,
+        value: &RegistryValue,
+    ) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let values = state
+            .registry
+            .get_mut(&view_key_id(hive, view, subkey))
+            .ok_or_else(|| KEY_NOT_FOUND.to_string())?;
+        values.insert(value_name.to_lowercase(), value.clone());
+        Ok(())
+    }
+
+    fn delete_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let values = state
+            .registry
+            .get_mut(&view_key_id(hive, view, subkey))
+            .ok_or_else(|| KEY_NOT_FOUND.to_string())?;
+        values
+            .remove(&value_name.to_lowercase())
+            .map(|_| ())
+            .ok_or_else(|| VALUE_NOT_FOUND.to_string())
+    }
+
+    fn interactive_user_sid(&self) -> Option<String> {
+        self.state.lock().unwrap().interactive_user_sid.clone()
+    }
+
+    fn active_power_scheme(&self) -> Result<Guid, String> {
+        Ok(self.state.lock().unwrap().power_scheme)
+    }
+
+    fn set_active_power_scheme(&self, scheme: Guid) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if !state.power_schemes.iter().any(|s| s.guid == scheme) {
+            return Err(format!("Power scheme {} is not installed", scheme));
+        }
+        state.power_scheme = scheme;
+        Ok(())
+    }
+
+    fn power_schemes(&self) -> Result<Vec<PowerSchemeInfo>, String> {
+        Ok(self.state.lock().unwrap().power_schemes.clone())
+    }
+
+    fn duplicate_power_scheme(&self, source: Guid, copy: Guid) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if state.power_schemes.iter().any(|s| s.guid == copy) {
+            return Err(format!("Power scheme {} already exists", copy));
+        }
+        let name = match state.power_schemes.iter().find(|s| s.guid == source) {
+            Some(scheme) => scheme.name.clone(),
+            None if source == Guid::from_u128(ULTIMATE_PERFORMANCE.0) => ULTIMATE_PERFORMANCE.1.to_string(),
+            None => return Err(format!("Power scheme {} does not exist", source)),
+        };
+        state.power_schemes.push(PowerSchemeInfo { guid: copy, name });
+        Ok(())
+    }
+
+    fn power_mode(&self) -> Result<Guid, String> {
+        self.state
+            .lock()
+            .unwrap()
+            .power_mode
+            .ok_or_else(|| "Power mode API not available on this Windows version".to_string())
+    }
+
+    fn set_power_mode(&self, mode: Guid) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if state.power_mode.is_none() {
+            return Err("Power mode API not available on this Windows version".to_string());
+        }
+        state.power_mode = Some(mode);
+        Ok(())
+    }
+
+    fn power_setting(&self, scheme: Guid, subgroup: Guid, setting: Guid) -> Result<PowerSettingValues, String> {
+        self.state
+            .lock()
+            .unwrap()
+            .power_settings
+            .get(&(scheme, subgroup, setting))
+            .copied()
+            .ok_or_else(|| POWER_SETTING_NOT_FOUND.to_string())
+    }
+
+    fn set_power_setting(
+        &self,
+        scheme: Guid,
+        subgroup: Guid,
+        setting: Guid,
+        source: PowerSource,
+        value: u32,
+    ) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let values = state
+            .power_settings
+            .get_mut(&(scheme, subgroup, setting))
+            .ok_or_else(|| POWER_SETTING_NOT_FOUND.to_string())?;
+
+        match source {
+            PowerSource::Ac => values.ac = value,
+            PowerSource::Dc => values.dc = value,
+        }
+        Ok(())
+    }
+
+    fn running_processes(&self) -> Result<Vec<ProcessInfo>, String> {
+        Ok(self.state.lock().unwrap().processes.clone())
+    }
+
+    fn process_path(&self, pid: u32) -> Result<String, String> {
+        self.state
+            .lock()
+            .unwrap()
+            .process_paths
+            .get(&pid)
+            .cloned()
+            .ok_or_else(|| format!("Failed to open process {}", pid))
+    }
+
+    fn terminate_process(&self, pid: u32) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let before = state.processes.len();
+        state.processes.retain(|p| p.pid != pid);
+        if state.processes.len() == before {
+            return Err(format!("Failed to open process {}", pid));
+        }
+        state.process_paths.remove(&pid);
+        Ok(())
+    }
+
+    fn memory_status(&self) -> Result<MemoryStatus, String> {
+        Ok(self.state.lock().unwrap().memory)
+    }
+
+    fn disk_space(&self, path: &str) -> Result<DiskSpace, String> {
+        let root = volume_root(path);
+        let state = self.state.lock().unwrap();
+        state
+            .volumes
+            .iter()
+            .find(|(space, _)| space.volume == root)
+            .map(|(space, _)| space.clone())
+            .ok_or_else(|| format!("No volume found for '{}'", path))
+    }
+
+    fn drive_kind(&self, path: &str) -> Result<DriveKind, String> {
+        let root = volume_root(path);
+        let state = self.state.lock().unwrap();
+        state
+            .volumes
+            .iter()
+            .find(|(space, _)| space.volume == root)
+            .map(|(_, kind)| *kind)
+            .ok_or_else(|| format!("No volume found for '{}'", path))
+    }
+
+    fn cpu_usage(&self, window: Duration) -> Result<CpuUsage, String> {
+        let state = self.state.lock().unwrap();
+        if state.cpu_sampled_for.is_some_and(|sampled| sampled < window) {
+            return Err(CPU_NOT_SAMPLED.to_string());
+        }
+        let mut processes: Vec<ProcessCpu> = state
+            .processes
+            .iter()
+            .filter_map(|p| {
+                let percent = *state.cpu_load.get(&p.pid)?;
+                Some(ProcessCpu {
+                    pid: p.pid,
+                    name: p.name.clone(),
+                    percent,
+                })
+            })
+            .collect();
+        processes.sort_by(|a, b| b.percent.total_cmp(&a.percent));
+
+        Ok(CpuUsage {
+            busy_percent: processes.iter().map(|p| p.percent).sum::<f64>().min(100.0),
+            processes,
+        })
+    }
+
+    fn displays(&self) -> Result<Vec<DisplayInfo>, String> {
+        Ok(self.state.lock().unwrap().displays.clone())
+    }
+
+    fn set_hdr_enabled(&self, device_name: &str, enabled: bool) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let display = state
+            .displays
+            .iter_mut()
+            .find(|d| d.device_name.eq_ignore_ascii_case(device_name))
+            .ok_or_else(|| format!("Display {} is not active", device_name))?;
+        if !display.hdr_supported {
+            return Err(format!("{} does not support HDR", device_name));
+        }
+        display.hdr_enabled = enabled;
+        Ok(())
+    }
+
+    fn display_modes(&self, device_name: &str) -> Result<Vec<DisplayMode>, String> {
+        let state = self.state.lock().unwrap();
+        let display = state
+            .displays
+            .iter()
+            .find(|d| d.device_name.eq_ignore_ascii_case(device_name))
+            .ok_or_else(|| format!("Display {} is not active", device_name))?;
+        Ok(state
+            .display_modes
+            .get(&display.device_name)
+            .cloned()
+            .unwrap_or_else(|| vec![display.mode]))
+    }
+
+    fn set_display_mode(&self, device_name: &str, mode: DisplayMode) -> Result<(), String> {
+        if !self.display_modes(device_name)?.contains(&mode) {
+            return Err(format!("{} does not support {}", device_name, mode));
+        }
+        let mut state = self.state.lock().unwrap();
+        if let Some(display) = state.displays.iter_mut().find(|d| d.device_name.eq_ignore_ascii_case(device_name)) {
+            display.mode = mode;
+        }
+        Ok(())
+    }
+
+    fn service_status(&self, name: &str) -> Result<ServiceStatus, String> {
+        self.state
+            .lock()
+            .unwrap()
+            .services
+            .get(&name.to_lowercase())
+            .copied()
+            .ok_or_else(|| SERVICE_NOT_FOUND.to_string())
+    }
+
+    fn set_service_running(&self, name: &str, running: bool) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let service = state
+            .services
+            .get_mut(&name.to_lowercase())
+            .ok_or_else(|| SERVICE_NOT_FOUND.to_string())?;
+
+        if running && service.start_type == ServiceStartType::Disabled {
+            return Err(format!("{} is disabled", name));
+        }
+        service.state = if running { ServiceState::Running } else { ServiceState::Stopped };
+        Ok(())
+    }
+
+    fn set_service_start_type(&self, name: &str, start_type: ServiceStartType) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let service = state
+            .services
+            .get_mut(&name.to_lowercase())
+            .ok_or_else(|| SERVICE_NOT_FOUND.to_string())?;
+
+        service.start_type = start_type;
+        Ok(())
+    }
+
+    fn scheduled_task(&self, path: &str) -> Result<TaskStatus, String> {
+        self.state
+            .lock()
+            .unwrap()
+            .tasks
+            .get(&task_id(path))
+            .copied()
+            .ok_or_else(|| TASK_NOT_FOUND.to_string())
+    }
+
+    fn set_task_enabled(&self, path: &str, enabled: bool) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let task = state.tasks.get_mut(&task_id(path)).ok_or_else(|| TASK_NOT_FOUND.to_string())?;
+        task.enabled = enabled;
+        Ok(())
+    }
+
+    fn stop_task(&self, path: &str) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        let task = state.tasks.get_mut(&task_id(path)).ok_or_else(|| TASK_NOT_FOUND.to_string())?;
+        task.running = false;
+        Ok(())
+    }
+
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        Ok(self.state.lock().unwrap().power_status)
+    }
+
+    fn chassis(&self) -> Result<Chassis, String> {
+        Ok(self.state.lock().unwrap().chassis)
+    }
+}
@@ -0,0 +1,604 @@
+//! System probe abstraction
+//!
+//! Checkers and the fixer never talk to the operating system directly; they
+//! read and modify system state through a [`SystemProbe`]. The Win32 backend
+//! talks to the live machine, while [`FakeProbe`] keeps everything in memory
+//! so checks can be scripted and exercised on any platform.
+
+pub mod cached;
+pub mod fake;
+pub mod snapshot;
+#[cfg(windows)]
+pub mod win32;
+
+pub use cached::ProcessCacheProbe;
+pub use fake::FakeProbe;
+pub use snapshot::{RecordingProbe, ReplayProbe, Snapshot};
+#[cfg(windows)]
+pub use win32::Win32Probe;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A GUID stored as a plain 128-bit value (power schemes, overlays)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Guid(pub u128);
+
+impl Guid {
+    pub const fn from_u128(value: u128) -> Self {
+        Self(value)
+    }
+
+    pub const fn to_u128(self) -> u128 {
+        self.0
+    }
+}
+
+impl fmt::Display for Guid {
+    /// Formats as `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` (lowercase, no braces)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let v = self.0;
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            (v >> 96) as u32,
+            (v >> 80) as u16,
+            (v >> 64) as u16,
+            (v >> 48) as u16,
+            v & 0xffff_ffff_ffff,
+        )
+    }
+}
+
+impl FromStr for Guid {
+    type Err = String;
+
+    /// Parses `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`, with or without braces
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim().trim_start_matches('{').trim_end_matches('}');
+        let hex: String = trimmed.chars().filter(|&c| c != '-').collect();
+
+        if hex.len() != 32 || trimmed.len() != 36 {
+            return Err(format!("Invalid GUID: {}", s));
+        }
+
+        u128::from_str_radix(&hex, 16)
+            .map(Guid)
+            .map_err(|_| format!("Invalid GUID: {}", s))
+    }
+}
+
+impl Serialize for Guid {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Guid {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Error every backend returns when a registry key does not exist
+pub const KEY_NOT_FOUND: &str = "Key not found";
+
+/// Error every backend returns when a key exists but the value does not
+pub const VALUE_NOT_FOUND: &str = "Value not found";
+
+/// Root hive of a registry path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryHive {
+    CurrentUser,
+    LocalMachine,
+    /// HKEY_USERS; the first subkey segment names the user (a SID or `.DEFAULT`)
+    Users,
+    ClassesRoot,
+    CurrentConfig,
+}
+
+/// Registry view to open a key in on 64-bit Windows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum RegistryView {
+    /// The view matching this process (64-bit for the shipped binary)
+    #[default]
+    #[serde(rename = "native")]
+    Native,
+    /// 32-bit view, where `SOFTWARE` keys live under `WOW6432Node`
+    #[serde(rename = "32")]
+    Bit32,
+    #[serde(rename = "64")]
+    Bit64,
+}
+
+impl RegistryView {
+    pub fn is_native(&self) -> bool {
+        *self == RegistryView::Native
+    }
+}
+
+impl FromStr for RegistryView {
+    type Err = String;
+
+    /// Parses `32`, `64` or `native` (empty also means native)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "" | "native" => Ok(RegistryView::Native),
+            "32" => Ok(RegistryView::Bit32),
+            "64" => Ok(RegistryView::Bit64),
+            other => Err(format!("invalid registry view '{}' (use 32 or 64)", other)),
+        }
+    }
+}
+
+/// Typed data of a registry value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistryValue {
+    Dword(u32),
+    Qword(u64),
+    String(String),
+    ExpandString(String),
+    MultiString(Vec<String>),
+    Binary(Vec<u8>),
+}
+
+/// Storage type of a registry value, independent of its data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RegistryValueType {
+    Dword,
+    Qword,
+    String,
+    ExpandString,
+    MultiString,
+    Binary,
+}
+
+impl RegistryValue {
+    pub fn value_type(&self) -> RegistryValueType {
+        match self {
+            RegistryValue::Dword(_) => RegistryValueType::Dword,
+            RegistryValue::Qword(_) => RegistryValueType::Qword,
+            RegistryValue::String(_) => RegistryValueType::String,
+            RegistryValue::ExpandString(_) => RegistryValueType::ExpandString,
+            RegistryValue::MultiString(_) => RegistryValueType::MultiString,
+            RegistryValue::Binary(_) => RegistryValueType::Binary,
+        }
+    }
+}
+
+impl fmt::Display for RegistryValueType {
+    /// Formats as the Win32 type name (e.g. `REG_DWORD`)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            RegistryValueType::Dword => "REG_DWORD",
+            RegistryValueType::Qword => "REG_QWORD",
+            RegistryValueType::String => "REG_SZ",
+            RegistryValueType::ExpandString => "REG_EXPAND_SZ",
+            RegistryValueType::MultiString => "REG_MULTI_SZ",
+            RegistryValueType::Binary => "REG_BINARY",
+        })
+    }
+}
+
+/// A running process as reported by the probe
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// Executable file name (e.g. "chrome.exe")
+    pub name: String,
+}
+
+/// Error a backend returns from [`SystemProbe::cpu_usage`] until it has
+/// sampled for a full window
+pub const CPU_NOT_SAMPLED: &str = "CPU load not measured yet";
+
+/// CPU utilization measured over a sampling window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CpuUsage {
+    /// Share of all logical processors' time spent busy
+    pub busy_percent: f64,
+    /// Processes that used CPU time in the window, busiest first
+    pub processes: Vec<ProcessCpu>,
+}
+
+/// One process's share of the total CPU time in a sampling window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProcessCpu {
+    pub pid: u32,
+    pub name: String,
+    pub percent: f64,
+}
+
+/// Physical memory and commit charge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryStatus {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+    /// Virtual memory committed by all processes
+    pub commit_bytes: u64,
+    /// Most memory that can be committed (RAM plus pagefiles)
+    pub commit_limit_bytes: u64,
+}
+
+/// Size and free space of the volume a path is on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiskSpace {
+    /// Root of the volume (e.g. `D:\`)
+    pub volume: String,
+    /// Free space the current user can use (quotas apply)
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+}
+
+/// Kind of storage a volume sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriveKind {
+    Nvme,
+    /// Solid-state drive on a bus other than NVMe (SATA, USB)
+    Ssd,
+    Hdd,
+    Removable,
+    Network,
+}
+
+impl DriveKind {
+    /// Name as written in the config
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DriveKind::Nvme => "nvme",
+            DriveKind::Ssd => "ssd",
+            DriveKind::Hdd => "hdd",
+            DriveKind::Removable => "removable",
+            DriveKind::Network => "network",
+        }
+    }
+}
+
+impl fmt::Display for DriveKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DriveKind::Nvme => "NVMe SSD",
+            DriveKind::Ssd => "SSD",
+            DriveKind::Hdd => "HDD",
+            DriveKind::Removable => "Removable drive",
+            DriveKind::Network => "Network drive",
+        })
+    }
+}
+
+/// Current mode of a display
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayMode {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_hz: u32,
+}
+
+impl fmt::Display for DisplayMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{} @ {}Hz", self.width, self.height, self.refresh_hz)
+    }
+}
+
+/// An active (desktop-attached) display
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DisplayInfo {
+    /// GDI device name, e.g. `\\.\DISPLAY1`
+    pub device_name: String,
+    /// Monitor model from its EDID, empty if unknown
+    pub model: String,
+    pub primary: bool,
+    pub mode: DisplayMode,
+    /// Bits per color channel sent to the monitor (8, 10, 12)
+    pub bits_per_channel: u32,
+    /// Effective DPI scaling in percent (100 = 96 DPI)
+    pub scale_percent: u32,
+    pub orientation: DisplayOrientation,
+    /// Whether the display can run in HDR (advanced color) mode
+    pub hdr_supported: bool,
+    pub hdr_enabled: bool,
+}
+
+/// Rotation of a display's desktop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayOrientation {
+    Landscape,
+    Portrait,
+    LandscapeFlipped,
+    PortraitFlipped,
+}
+
+impl DisplayOrientation {
+    /// The orientation as written in the config
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisplayOrientation::Landscape => "landscape",
+            DisplayOrientation::Portrait => "portrait",
+            DisplayOrientation::LandscapeFlipped => "landscape_flipped",
+            DisplayOrientation::PortraitFlipped => "portrait_flipped",
+        }
+    }
+}
+
+impl fmt::Display for DisplayOrientation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DisplayOrientation::Landscape => "Landscape",
+            DisplayOrientation::Portrait => "Portrait",
+            DisplayOrientation::LandscapeFlipped => "Landscape (flipped)",
+            DisplayOrientation::PortraitFlipped => "Portrait (flipped)",
+        })
+    }
+}
+
+/// An installed power scheme
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerSchemeInfo {
+    pub guid: Guid,
+    /// Friendly name as shown in Control Panel
+    pub name: String,
+}
+
+/// Error every backend returns when a scheme has no value for a power setting
+pub const POWER_SETTING_NOT_FOUND: &str = "Power setting not found";
+
+/// Power source a power setting value applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PowerSource {
+    /// Plugged in
+    Ac,
+    /// On battery
+    Dc,
+}
+
+impl fmt::Display for PowerSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PowerSource::Ac => "AC",
+            PowerSource::Dc => "DC",
+        })
+    }
+}
+
+/// AC and DC value indices of a power setting in one scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerSettingValues {
+    pub ac: u32,
+    pub dc: u32,
+}
+
+impl PowerSettingValues {
+    pub fn get(&self, source: PowerSource) -> u32 {
+        match source {
+            PowerSource::Ac => self.ac,
+            PowerSource::Dc => self.dc,
+        }
+    }
+}
+
+/// Error every backend returns when a service is not installed
+pub const SERVICE_NOT_FOUND: &str = "Service not installed";
+
+/// Run state of a Windows service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceState {
+    Running,
+    Stopped,
+    Paused,
+    /// Starting, stopping, pausing or resuming
+    Pending,
+}
+
+/// How a Windows service is started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServiceStartType {
+    Boot,
+    System,
+    #[serde(alias = "auto")]
+    Automatic,
+    Manual,
+    Disabled,
+}
+
+/// Current state and start type of a service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceStatus {
+    pub state: ServiceState,
+    pub start_type: ServiceStartType,
+}
+
+impl fmt::Display for ServiceState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ServiceState::Running => "Running",
+            ServiceState::Stopped => "Stopped",
+            ServiceState::Paused => "Paused",
+            ServiceState::Pending => "Pending",
+        })
+    }
+}
+
+impl fmt::Display for ServiceStartType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ServiceStartType::Boot => "Boot",
+            ServiceStartType::System => "System",
+            ServiceStartType::Automatic => "Automatic",
+            ServiceStartType::Manual => "Manual",
+            ServiceStartType::Disabled => "Disabled",
+        })
+    }
+}
+
+/// Error every backend returns when a scheduled task does not exist
+pub const TASK_NOT_FOUND: &str = "Scheduled task not found";
+
+/// Current state of a Task Scheduler task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaskStatus {
+    pub enabled: bool,
+    pub running: bool,
+}
+
+/// AC line and battery state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PowerStatus {
+    /// Running on mains power; `None` if Windows can't tell
+    pub ac_online: Option<bool>,
+    /// Remaining charge of the system battery; `None` without one
+    pub battery_percent: Option<u8>,
+}
+
+/// Kind of machine, as far as battery-related checks are concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Chassis {
+    /// Has a lid or an internal battery
+    Laptop,
+    Desktop,
+}
+
+/// Access to the system state that checks read and fixes modify
+///
+/// Errors are human-readable strings; checkers surface them unchanged in
+/// `CheckResult::error`, so messages should make sense to the end user.
+pub trait SystemProbe: Send + Sync {
+    /// Read a registry value with the type it is stored as
+    ///
+    /// Fails with [`KEY_NOT_FOUND`] or [`VALUE_NOT_FOUND`] when missing; an
+    /// empty `value_name` reads the key's default value.
+    fn read_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str)
+        -> Result<RegistryValue, String>;
+
+    /// Write a registry value (replacing its type) in an existing key
+    fn write_value(
+        &self,
+        hive: RegistryHive,
+        view: RegistryView,
+        subkey: &str,
+        value_name: &str,
+        value: &RegistryValue,
+    ) -> Result<(), String>;
+
+    /// Delete a value from an existing key
+    fn delete_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str)
+        -> Result<(), String>;
+
+    /// SID of the interactive user when this process runs elevated as a
+    /// different account; HKCU paths are then read from `HKU\<sid>`
+    fn interactive_user_sid(&self) -> Option<String>;
+
+    /// GUID of the active power scheme
+    fn active_power_scheme(&self) -> Result<Guid, String>;
+
+    /// Activate a power scheme by GUID
+    fn set_active_power_scheme(&self, scheme: Guid) -> Result<(), String>;
+
+    /// Installed power schemes (hidden ones like Ultimate Performance are
+    /// only listed once duplicated)
+    fn power_schemes(&self) -> Result<Vec<PowerSchemeInfo>, String>;
+
+    /// Copy a power scheme (built-in templates included) under the GUID `copy`
+    fn duplicate_power_scheme(&self, source: Guid, copy: Guid) -> Result<(), String>;
+
+    /// GUID of the active power mode overlay (all zeros = balanced)
+    fn power_mode(&self) -> Result<Guid, String>;
+
+    /// Activate a power mode overlay by GUID
+    fn set_power_mode(&self, mode: Guid) -> Result<(), String>;
+
+    /// AC and DC value indices of a setting in a power scheme
+    ///
+    /// Fails with [`POWER_SETTING_NOT_FOUND`] when the scheme has no such setting.
+    fn power_setting(&self, scheme: Guid, subgroup: Guid, setting: Guid) -> Result<PowerSettingValues, String>;
+
+    /// Write one value index of a setting in a power scheme (re-applied if
+    /// the scheme is active)
+    fn set_power_setting(
+        &self,
+        scheme: Guid,
+        subgroup: Guid,
+        setting: Guid,
+        source: PowerSource,
+        value: u32,
+    ) -> Result<(), String>;
+
+    /// List running processes
+    fn running_processes(&self) -> Result<Vec<ProcessInfo>, String>;
+
+    /// Full image path of a running process
+    ///
+    /// Listing processes leaves paths out because each lookup opens the
+    /// process; callers resolve them only for the processes they care about.
+    fn process_path(&self, pid: u32) -> Result<String, String>;
+
+    /// Terminate a single process by PID
+    fn terminate_process(&self, pid: u32) -> Result<(), String>;
+
+    /// System and per-process CPU utilization over at least `window`
+    ///
+    /// Measures from samples taken in the background, so it doesn't block:
+    /// until sampling has run for `window` it fails with [`CPU_NOT_SAMPLED`].
+    fn cpu_usage(&self, window: Duration) -> Result<CpuUsage, String>;
+
+    /// Physical memory and commit charge
+    fn memory_status(&self) -> Result<MemoryStatus, String>;
+
+    /// Free space on the volume holding a path (a drive letter, directory or
+    /// file; it doesn't have to exist yet)
+    fn disk_space(&self, path: &str) -> Result<DiskSpace, String>;
+
+    /// Kind of drive the volume holding a path is on
+    fn drive_kind(&self, path: &str) -> Result<DriveKind, String>;
+
+    /// Active displays, primary first
+    fn displays(&self) -> Result<Vec<DisplayInfo>, String>;
+
+    /// Turn HDR (advanced color) on or off for a display by its GDI device name
+    fn set_hdr_enabled(&self, device_name: &str, enabled: bool) -> Result<(), String>;
+
+    /// Modes the driver offers for a display
+    fn display_modes(&self, device_name: &str) -> Result<Vec<DisplayMode>, String>;
+
+    /// Switch a display to another mode, checking with the driver before applying it
+    fn set_display_mode(&self, device_name: &str, mode: DisplayMode) -> Result<(), String>;
+
+    /// State and start type of a service by its service (not display) name
+    ///
+    /// Fails with [`SERVICE_NOT_FOUND`] when the service isn't installed.
+    fn service_status(&self, name: &str) -> Result<ServiceStatus, String>;
+
+    /// Start or stop a service, waiting until it has done so
+    fn set_service_running(&self, name: &str, running: bool) -> Result<(), String>;
+
+    /// Change how a service is started
+    fn set_service_start_type(&self, name: &str, start_type: ServiceStartType) -> Result<(), String>;
+
+    /// State of a scheduled task by its full path (`\Microsoft\Windows\...`;
+    /// a bare name is looked up in the root folder)
+    ///
+    /// Fails with [`TASK_NOT_FOUND`] when there is no such task.
+    fn scheduled_task(&self, path: &str) -> Result<TaskStatus, String>;
+
+    /// Enable or disable a scheduled task (running instances keep running)
+    fn set_task_enabled(&self, path: &str, enabled: bool) -> Result<(), String>;
+
+    /// Stop all running instances of a scheduled task
+    fn stop_task(&self, path: &str) -> Result<(), String>;
+
+    /// Whether the machine runs on AC power and how charged its battery is
+    fn power_status(&self) -> Result<PowerStatus, String>;
+
+    /// Whether this is a laptop or a desktop
+    fn chassis(&self) -> Result<Chassis, String>;
+}
@@ -0,0 +1,599 @@
+//! System-state snapshots for auditing and offline replay
+//!
+//! [`RecordingProbe`] wraps a live probe and records every value a check
+//! reads. The resulting [`Snapshot`] is saved as JSON next to a benchmark
+//! result and can later be evaluated against any scenario with
+//! [`ReplayProbe`], which answers reads from the file and refuses writes.
+
+use crate::probe::{
+    Chassis, CpuUsage, DiskSpace, DisplayInfo, DisplayMode, DriveKind, Guid, MemoryStatus, PowerSchemeInfo,
+    PowerSettingValues, PowerSource, PowerStatus, ProcessInfo, RegistryHive, RegistryValue, RegistryView,
+    ServiceStartType, ServiceStatus, SystemProbe, TaskStatus,
+};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Error for reads the snapshot has no data for
+const NOT_CAPTURED: &str = "Not captured in snapshot";
+
+/// Outcome of a recorded read: the value, or the error the live probe returned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recorded<T> {
+    Value(T),
+    Error(String),
+}
+
+impl<T: Clone> Recorded<T> {
+    fn from_result(result: &Result<T, String>) -> Self {
+        match result {
+            Ok(value) => Recorded::Value(value.clone()),
+            Err(e) => Recorded::Error(e.clone()),
+        }
+    }
+
+    fn to_result(&self) -> Result<T, String> {
+        match self {
+            Recorded::Value(value) => Ok(value.clone()),
+            Recorded::Error(e) => Err(e.clone()),
+        }
+    }
+}
+
+/// A single recorded registry read
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryRead {
+    pub hive: RegistryHive,
+    #[serde(default, skip_serializing_if = "RegistryView::is_native")]
+    pub view: RegistryView,
+    pub key: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub result: Recorded<RegistryValue>,
+}
+
+impl RegistryRead {
+    fn matches(&self, hive: RegistryHive, view: RegistryView, key: &str, name: &str) -> bool {
+        self.hive == hive
+            && self.view == view
+            && self.key.eq_ignore_ascii_case(key) && self.name.eq_ignore_ascii_case(name)
+    }
+}
+
+/// A recorded read of a named item (service, scheduled task, ...)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamedRead<T> {
+    pub name: String,
+    #[serde(flatten)]
+    pub result: Recorded<T>,
+}
+
+/// Record a named read, replacing an earlier read of the same name
+fn record_named<T: Clone>(reads: &mut Vec<NamedRead<T>>, name: &str, result: &Result<T, String>) {
+    let read = NamedRead {
+        name: name.to_string(),
+        result: Recorded::from_result(result),
+    };
+    match reads.iter_mut().find(|r| r.name.eq_ignore_ascii_case(name)) {
+        Some(existing) => *existing = read,
+        None => reads.push(read),
+    }
+}
+
+/// Key of a power setting read
+fn power_setting_key(scheme: Guid, subgroup: Guid, setting: Guid) -> String {
+    format!("{}/{}/{}", scheme, subgroup, setting)
+}
+
+/// Replay a named read (names are case-insensitive)
+fn replayed_named<T: Clone>(reads: &[NamedRead<T>], name: &str) -> Result<T, String> {
+    reads
+        .iter()
+        .find(|r| r.name.eq_ignore_ascii_case(name))
+        .map(|r| r.result.to_result())
+        .unwrap_or_else(|| Err(NOT_CAPTURED.to_string()))
+}
+
+/// Everything the checks of a scenario read from the system
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// RFC 3339 capture time
+    pub captured_at: String,
+    /// Machine name the snapshot was taken on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Scenario whose checks were recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<String>,
+    /// Interactive user HKCU was redirected to, if the capture ran elevated as another account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interactive_user_sid: Option<String>,
+    #[serde(default)]
+    pub registry: Vec<RegistryRead>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_scheme: Option<Recorded<Guid>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_mode: Option<Recorded<Guid>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_schemes: Option<Recorded<Vec<PowerSchemeInfo>>>,
+    /// Power setting reads keyed by `<scheme>/<subgroup>/<setting>`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub power_settings: Vec<NamedRead<PowerSettingValues>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processes: Option<Recorded<Vec<ProcessInfo>>>,
+    /// Image path reads keyed by PID
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub process_paths: Vec<NamedRead<String>>,
+    /// One CPU sample, replayed whatever window is asked for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_usage: Option<Recorded<CpuUsage>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Recorded<MemoryStatus>>,
+    /// Disk space reads keyed by the path the check named
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub disk_space: Vec<NamedRead<DiskSpace>>,
+    /// Drive kind reads keyed by the path the check named
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub drive_kinds: Vec<NamedRead<DriveKind>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub displays: Option<Recorded<Vec<DisplayInfo>>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub services: Vec<NamedRead<ServiceStatus>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tasks: Vec<NamedRead<TaskStatus>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub power_status: Option<Recorded<PowerStatus>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chassis: Option<Recorded<Chassis>>,
+}
+
+impl Snapshot {
+    /// Load a snapshot from a JSON file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read snapshot file: {:?}", path))?;
+
+        serde_json::from_str(&content).with_context(|| "Failed to parse snapshot JSON")
+    }
+
+    /// Save the snapshot as pretty-printed JSON
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize snapshot")?;
+
+        fs::write(path, content)
+            .with_context(|| format!("Failed to write snapshot file: {:?}", path))
+    }
+
+    fn registry_read(&self, hive: RegistryHive, view: RegistryView, key: &str, name: &str) -> Result<RegistryValue, String> {
+        self.registry
+            .iter()
+            .find(|r| r.matches(hive, view, key, name))
+            .map(|r| r.result.to_result())
+            .unwrap_or_else(|| Err(NOT_CAPTURED.to_string()))
+    }
+}
+
+/// Probe wrapper that records every read into a [`Snapshot`]
+///
+/// Writes are refused so that capturing a snapshot never changes the system.
+pub struct RecordingProbe<'a> {
+    inner: &'a dyn SystemProbe,
+    snapshot: Mutex<Snapshot>,
+}
+
+impl<'a> RecordingProbe<'a> {
+    pub fn new(inner: &'a dyn SystemProbe) -> Self {
+        Self {
+            inner,
+            snapshot: Mutex::new(Snapshot {
+                captured_at: chrono::Local::now().to_rfc3339(),
+                host: std::env::var("COMPUTERNAME").ok(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Finish recording and return the snapshot
+    pub fn into_snapshot(self) -> Snapshot {
+        self.snapshot.into_inner().unwrap()
+    }
+
+    fn record_registry(
+        &self,
+        hive: RegistryHive,
+        view: RegistryView,
+        key: &str,
+        name: &str,
+        result: Result<RegistryValue, String>,
+    ) {
+        let read = RegistryRead {
+            hive,
+            view,
+            key: key.to_string(),
+            name: name.to_string(),
+            result: Recorded::from_result(&result),
+        };
+
+        let mut snapshot = self.snapshot.lock().unwrap();
+        match snapshot.registry.iter_mut().find(|r| r.matches(hive, view, key, name)) {
+            Some(existing) => *existing = read,
+            None => snapshot.registry.push(read),
+        }
+    }
+}
+
+impl SystemProbe for RecordingProbe<'_> {
+    fn read_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<RegistryValue, String> {
+        let result = self.inner.read_value(hive, view, subkey, value_name);
+        self.record_registry(hive, view, subkey, value_name, result.clone());
+        result
+    }
+
+    fn write_value(
+        &self,
+        _hive: RegistryHive,
+        _view: RegistryView,
+        _subkey: &str,
+        _value_name: &str,
+        _value: &RegistryValue,
+    ) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn delete_value(&self, _hive: RegistryHive, _view: RegistryView, _subkey: &str, _value_name: &str) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn interactive_user_sid(&self) -> Option<String> {
+        let sid = self.inner.interactive_user_sid();
+        self.snapshot.lock().unwrap().interactive_user_sid = sid.clone();
+        sid
+    }
+
+    fn active_power_scheme(&self) -> Result<Guid, String> {
+        let result = self.inner.active_power_scheme();
+        self.snapshot.lock().unwrap().power_scheme = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn set_active_power_scheme(&self, _scheme: Guid) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn power_schemes(&self) -> Result<Vec<PowerSchemeInfo>, String> {
+        let result = self.inner.power_schemes();
+        self.snapshot.lock().unwrap().power_schemes = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn duplicate_power_scheme(&self, _source: Guid, _copy: Guid) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn power_mode(&self) -> Result<Guid, String> {
+        let result = self.inner.power_mode();
+        self.snapshot.lock().unwrap().power_mode = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn set_power_mode(&self, _mode: Guid) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn power_setting(&self, scheme: Guid, subgroup: Guid, setting: Guid) -> Result<PowerSettingValues, String> {
+        let result = self.inner.power_setting(scheme, subgroup, setting);
+        let key = power_setting_key(scheme, subgroup, setting);
+        record_named(&mut self.snapshot.lock().unwrap().power_settings, &key, &result);
+        result
+    }
+
+    fn set_power_setting(
+        &self,
+        _scheme: Guid,
+        _subgroup: Guid,
+        _setting: Guid,
+        _source: PowerSource,
+        _value: u32,
+    ) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn running_processes(&self) -> Result<Vec<ProcessInfo>, String> {
+        let result = self.inner.running_processes();
+        self.snapshot.lock().unwrap().processes = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn process_path(&self, pid: u32) -> Result<String, String> {
+        let result = self.inner.process_path(pid);
+        record_named(&mut self.snapshot.lock().unwrap().process_paths, &pid.to_string(), &result);
+        result
+    }
+
+    fn terminate_process(&self, _pid: u32) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn cpu_usage(&self, window: Duration) -> Result<CpuUsage, String> {
+        let result = self.inner.cpu_usage(window);
+        self.snapshot.lock().unwrap().cpu_usage = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn memory_status(&self) -> Result<MemoryStatus, String> {
+        let result = self.inner.memory_status();
+        self.snapshot.lock().unwrap().memory = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn disk_space(&self, path: &str) -> Result<DiskSpace, String> {
+        let result = self.inner.disk_space(path);
+        record_named(&mut self.snapshot.lock().unwrap().disk_space, path, &result);
+        result
+    }
+
+    fn drive_kind(&self, path: &str) -> Result<DriveKind, String> {
+        let result = self.inner.drive_kind(path);
+        record_named(&mut self.snapshot.lock().unwrap().drive_kinds, path, &result);
+        result
+    }
+
+    fn displays(&self) -> Result<Vec<DisplayInfo>, String> {
+        let result = self.inner.displays();
+        self.snapshot.lock().unwrap().displays = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn set_hdr_enabled(&self, _device_name: &str, _enabled: bool) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn display_modes(&self, device_name: &str) -> Result<Vec<DisplayMode>, String> {
+        // Only fixers list modes, so there is nothing to record
+        self.inner.display_modes(device_name)
+    }
+
+    fn set_display_mode(&self, _device_name: &str, _mode: DisplayMode) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn service_status(&self, name: &str) -> Result<ServiceStatus, String> {
+        let result = self.inner.service_status(name);
+        record_named(&mut self.snapshot.lock().unwrap().services, name, &result);
+        result
+    }
+
+    fn set_service_running(&self, _name: &str, _running: bool) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn set_service_start_type(&self, _name: &str, _start_type: ServiceStartType) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn scheduled_task(&self, path: &str) -> Result<TaskStatus, String> {
+        let result = self.inner.scheduled_task(path);
+        record_named(&mut self.snapshot.lock().unwrap().tasks, path, &result);
+        result
+    }
+
+    fn set_task_enabled(&self, _path: &str, _enabled: bool) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn stop_task(&self, _path: &str) -> Result<(), String> {
+        Err("Snapshot capture is read-only".to_string())
+    }
+
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        let result = self.inner.power_status();
+        self.snapshot.lock().unwrap().power_status = Some(Recorded::from_result(&result));
+        result
+    }
+
+    fn chassis(&self) -> Result<Chassis, String> {
+        let result = self.inner.chassis();
+        self.snapshot.lock().unwrap().chassis = Some(Recorded::from_result(&result));
+        result
+    }
+}
+
+/// Read-only probe answering from a saved [`Snapshot`]
+///
+/// Reads the snapshot has no data for fail with "Not captured in snapshot".
+pub struct ReplayProbe {
+    snapshot: Snapshot,
+}
+
+impl ReplayProbe {
+    pub fn new(snapshot: Snapshot) -> Self {
+        Self { snapshot }
+    }
+}
+
+fn replayed<T: Clone>(recorded: &Option<Recorded<T>>) -> Result<T, String> {
+    recorded
+        .as_ref()
+        .map(|r| r.to_result())
+        .unwrap_or_else(|| Err(NOT_CAPTURED.to_string()))
+}
+
+impl SystemProbe for ReplayProbe {
+    fn read_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<RegistryValue, String> {
+        self.snapshot.registry_read(hive, view, subkey, value_name)
+    }
+
+    fn write_value(
+        &self,
+        _hive: RegistryHive,
+        _view: RegistryView,
+        _subkey: &str,
+        _value_name: &str,
+        _value: &RegistryValue,
+    ) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn delete_value(&self, _hive: RegistryHive, _view: RegistryView, _subkey: &str, _value_name: &str) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn interactive_user_sid(&self) -> Option<String> {
+        self.snapshot.interactive_user_sid.clone()
+    }
+
+    fn active_power_scheme(&self) -> Result<Guid, String> {
+        replayed(&self.snapshot.power_scheme)
+    }
+
+    fn set_active_power_scheme(&self, _scheme: Guid) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn power_schemes(&self) -> Result<Vec<PowerSchemeInfo>, String> {
+        replayed(&self.snapshot.power_schemes)
+    }
+
+    fn duplicate_power_scheme(&self, _source: Guid, _copy: Guid) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn power_mode(&self) -> Result<Guid, String> {
+        replayed(&self.snapshot.power_mode)
+    }
+
+    fn set_power_mode(&self, _mode: Guid) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn power_setting(&self, scheme: Guid, subgroup: Guid, setting: Guid) -> Result<PowerSettingValues, String> {
+        replayed_named(&self.snapshot.power_settings, &power_setting_key(scheme, subgroup, setting))
+    }
+
+    fn set_power_setting(
+        &self,
+        _scheme: Guid,
+        _subgroup: Guid,
+        _setting: Guid,
+        _source: PowerSource,
+        _value: u32,
+    ) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn running_processes(&self) -> Result<Vec<ProcessInfo>, String> {
+        replayed(&self.snapshot.processes)
+    }
+
+    fn process_path(&self, pid: u32) -> Result<String, String> {
+        replayed_named(&self.snapshot.process_paths, &pid.to_string())
+    }
+
+    fn terminate_process(&self, _pid: u32) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn cpu_usage(&self, _window: Duration) -> Result<CpuUsage, String> {
+        replayed(&self.snapshot.cpu_usage)
+    }
+
+    fn memory_status(&self) -> Result<MemoryStatus, String> {
+        replayed(&self.snapshot.memory)
+    }
+
+    fn disk_space(&self, path: &str) -> Result<DiskSpace, String> {
+        replayed_named(&self.snapshot.disk_space, path)
+    }
+
+    fn drive_kind(&self, path: &str) -> Result<DriveKind, String> {
+        replayed_named(&self.snapshot.drive_kinds, path)
+    }
+
+    fn displays(&self) -> Result<Vec<DisplayInfo>, String> {
+        replayed(&self.snapshot.displays)
+    }
+
+    fn set_hdr_enabled(&self, _device_name: &str, _enabled: bool) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn display_modes(&self, _device_name: &str) -> Result<Vec<DisplayMode>, String> {
+        Err("Snapshots do not record display modes".to_string())
+    }
+
+    fn set_display_mode(&self, _device_name: &str, _mode: DisplayMode) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn service_status(&self, name: &str) -> Result<ServiceStatus, String> {
+        replayed_named(&self.snapshot.services, name)
+    }
+
+    fn set_service_running(&self, _name: &str, _running: bool) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn set_service_start_type(&self, _name: &str, _start_type: ServiceStartType) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn scheduled_task(&self, path: &str) -> Result<TaskStatus, String> {
+        replayed_named(&self.snapshot.tasks, path)
+    }
+
+    fn set_task_enabled(&self, _path: &str, _enabled: bool) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn stop_task(&self, _path: &str) -> Result<(), String> {
+        Err("Snapshot replay is read-only".to_string())
+    }
+
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        replayed(&self.snapshot.power_status)
+    }
+
+    fn chassis(&self) -> Result<Chassis, String> {
+        replayed(&self.snapshot.chassis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkers::run_all_checks;
+    use crate::config::Config;
+    use crate::probe::FakeProbe;
+
+    #[test]
+    fn test_record_and_replay_matches_live_results() {
+        let live = FakeProbe::new()
+            .with_dword(RegistryHive::CurrentUser, "Software\\Microsoft\\GameBar", "AutoGameModeEnabled", 0)
+            .with_process("C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe")
+            .with_power_mode(None);
+
+        let config = Config::default();
+        let checks = config.get_scenario_checks().unwrap();
+
+        let recorder = RecordingProbe::new(&live);
+        let live_results = run_all_checks(&recorder, checks);
+        let snapshot = recorder.into_snapshot();
+
+        // Round-trip through JSON as a saved file would
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let replay = ReplayProbe::new(serde_json::from_str(&json).unwrap());
+        let replay_results = run_all_checks(&replay, checks);
+
+        assert_eq!(live_results.len(), replay_results.len());
+        for (live, replayed) in live_results.iter().zip(&replay_results) {
+            assert_eq!(live.passed, replayed.passed);
+            assert_eq!(live.current_value, replayed.current_value);
+            assert_eq!(live.message, replayed.message);
+        }
+    }
+}
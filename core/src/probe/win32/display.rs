@@ -0,0 +1,302 @@
+//! Win32 display settings access
+
+use super::registry::to_wide;
+use crate::probe::{DisplayInfo, DisplayMode, DisplayOrientation};
+use windows::core::PCWSTR;
+use windows::Win32::Devices::Display::{
+    DisplayConfigGetDeviceInfo, DisplayConfigSetDeviceInfo, GetDisplayConfigBufferSizes, QueryDisplayConfig,
+    DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO, DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME, DISPLAYCONFIG_DEVICE_INFO_SET_ADVANCED_COLOR_STATE,
+    DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO, DISPLAYCONFIG_MODE_INFO, DISPLAYCONFIG_PATH_INFO,
+    DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE, DISPLAYCONFIG_SOURCE_DEVICE_NAME, DISPLAYCONFIG_TARGET_DEVICE_NAME,
+    QDC_ONLY_ACTIVE_PATHS,
+};
+use windows::Win32::Foundation::{ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS, HWND, POINT};
+use windows::Win32::Graphics::Gdi::{
+    ChangeDisplaySettingsExW, EnumDisplaySettingsW, MonitorFromPoint, CDS_TEST, CDS_UPDATEREGISTRY, DEVMODEW,
+    DISP_CHANGE_RESTART, DISP_CHANGE_SUCCESSFUL, DMDO_180, DMDO_270, DMDO_90, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT,
+    DM_PELSWIDTH, ENUM_CURRENT_SETTINGS, ENUM_DISPLAY_SETTINGS_MODE, MONITOR_DEFAULTTONEAREST,
+};
+use windows::Win32::UI::HiDpi::{
+    GetDpiForMonitor, SetThreadDpiAwarenessContext, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, MDT_EFFECTIVE_DPI,
+};
+
+/// Enumerate the active displays, primary first
+pub fn displays() -> Result<Vec<DisplayInfo>, String> {
+    let mut displays: Vec<DisplayInfo> = Vec::new();
+
+    for path in active_paths()? {
+        let Some(device_name) = source_name(&path) else {
+            continue;
+        };
+        if displays.iter().any(|d| d.device_name == device_name) {
+            // Cloned outputs share a source; report it once
+            continue;
+        }
+        let Some(settings) = current_settings(&device_name) else {
+            continue;
+        };
+        let color = advanced_color(&path);
+        displays.push(DisplayInfo {
+            model: target_name(&path).unwrap_or_default(),
+            device_name,
+            primary: settings.position == POINT::default(),
+            mode: settings.mode,
+            bits_per_channel: color
+                .map(|c| c.bits_per_channel)
+                .filter(|&bits| bits > 0)
+                .unwrap_or(settings.bits_per_pixel.min(24) / 3),
+            scale_percent: scale_percent(settings.position),
+            orientation: settings.orientation,
+            hdr_supported: color.is_some_and(|c| c.supported),
+            hdr_enabled: color.is_some_and(|c| c.enabled),
+        });
+    }
+
+    if displays.is_empty() {
+        return Err("Failed to enumerate display settings".to_string());
+    }
+    displays.sort_by_key(|d| !d.primary);
+    Ok(displays)
+}
+
+/// List the modes a display's driver offers (32-bit color only, without duplicates)
+pub fn display_modes(device_name: &str) -> Result<Vec<DisplayMode>, String> {
+    let device = to_wide(device_name);
+    let mut modes: Vec<DisplayMode> = Vec::new();
+
+    for index in 0.. {
+        let mut devmode = DEVMODEW {
+            dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+            ..Default::default()
+        };
+        let found = unsafe {
+            EnumDisplaySettingsW(PCWSTR(device.as_ptr()), ENUM_DISPLAY_SETTINGS_MODE(index), &mut devmode).as_bool()
+        };
+        if !found {
+            break;
+        }
+        let mode = DisplayMode {
+            width: devmode.dmPelsWidth,
+            height: devmode.dmPelsHeight,
+            refresh_hz: devmode.dmDisplayFrequency,
+        };
+        if devmode.dmBitsPerPel >= 32 && !modes.contains(&mode) {
+            modes.push(mode);
+        }
+    }
+
+    if modes.is_empty() {
+        return Err(format!("No display modes reported for {}", device_name));
+    }
+    Ok(modes)
+}
+
+/// Switch a display's resolution and refresh rate, after the driver accepts it in a test
+pub fn set_display_mode(device_name: &str, mode: DisplayMode) -> Result<(), String> {
+    let device = to_wide(device_name);
+    let devmode = DEVMODEW {
+        dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+        dmFields: DM_PELSWIDTH | DM_PELSHEIGHT | DM_DISPLAYFREQUENCY,
+        dmPelsWidth: mode.width,
+        dmPelsHeight: mode.height,
+        dmDisplayFrequency: mode.refresh_hz,
+        ..Default::default()
+    };
+    let change = |flags| unsafe {
+        ChangeDisplaySettingsExW(PCWSTR(device.as_ptr()), Some(&devmode), HWND::default(), flags, None)
+    };
+
+    let tested = change(CDS_TEST);
+    if tested != DISP_CHANGE_SUCCESSFUL {
+        return Err(format!("{} rejected {} (error {})", device_name, mode, tested.0));
+    }
+    match change(CDS_UPDATEREGISTRY) {
+        DISP_CHANGE_SUCCESSFUL => Ok(()),
+        DISP_CHANGE_RESTART => Err(format!("{} needs a restart to switch to {}", device_name, mode)),
+        err => Err(format!("Failed to switch {} to {} (error {})", device_name, mode, err.0)),
+    }
+}
+
+/// Turn advanced color (HDR) on or off for the monitor showing a GDI device
+pub fn set_hdr_enabled(device_name: &str, enabled: bool) -> Result<(), String> {
+    let path = active_paths()?
+        .into_iter()
+        .find(|p| source_name(p).is_some_and(|name| name.eq_ignore_ascii_case(device_name)))
+        .ok_or_else(|| format!("Display {} is not active", device_name))?;
+
+    if !advanced_color(&path).is_some_and(|c| c.supported) {
+        return Err(format!("{} does not support HDR", device_name));
+    }
+
+    let mut request = DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE::default();
+    request.header.r#type = DISPLAYCONFIG_DEVICE_INFO_SET_ADVANCED_COLOR_STATE;
+    request.header.size = std::mem::size_of::<DISPLAYCONFIG_SET_ADVANCED_COLOR_STATE>() as u32;
+    request.header.adapterId = path.targetInfo.adapterId;
+    request.header.id = path.targetInfo.id;
+    // enableAdvancedColor is bit 0
+    request.Anonymous.value = enabled as u32;
+
+    let action = if enabled { "enable" } else { "disable" };
+    match unsafe { DisplayConfigSetDeviceInfo(&request.header) } {
+        0 => Ok(()),
+        err => Err(format!("Failed to {} HDR on {}: error {}", action, device_name, err)),
+    }
+}
+
+/// Query the active source/target paths, retrying if the topology changes mid-call
+fn active_paths() -> Result<Vec<DISPLAYCONFIG_PATH_INFO>, String> {
+    loop {
+        let mut path_count = 0u32;
+        let mut mode_count = 0u32;
+        let err = unsafe { GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count) };
+        if err != ERROR_SUCCESS {
+            return Err(format!("Failed to query display configuration: {:?}", err));
+        }
+
+        let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+        let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+        let err = unsafe {
+            QueryDisplayConfig(
+                QDC_ONLY_ACTIVE_PATHS,
+                &mut path_count,
+                paths.as_mut_ptr(),
+                &mut mode_count,
+                modes.as_mut_ptr(),
+                None,
+            )
+        };
+        match err {
+            ERROR_SUCCESS => {
+                paths.truncate(path_count as usize);
+                return Ok(paths);
+            }
+            ERROR_INSUFFICIENT_BUFFER => continue,
+            _ => return Err(format!("Failed to query display configuration: {:?}", err)),
+        }
+    }
+}
+
+/// GDI device name (`\\.\DISPLAYn`) of a path's source
+fn source_name(path: &DISPLAYCONFIG_PATH_INFO) -> Option<String> {
+    let mut request = DISPLAYCONFIG_SOURCE_DEVICE_NAME::default();
+    request.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME;
+    request.header.size = std::mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>() as u32;
+    request.header.adapterId = path.sourceInfo.adapterId;
+    request.header.id = path.sourceInfo.id;
+
+    if unsafe { DisplayConfigGetDeviceInfo(&mut request.header) } != 0 {
+        return None;
+    }
+    Some(wide_to_string(&request.viewGdiDeviceName))
+}
+
+/// Monitor friendly name (from its EDID) of a path's target
+fn target_name(path: &DISPLAYCONFIG_PATH_INFO) -> Option<String> {
+    let mut request = DISPLAYCONFIG_TARGET_DEVICE_NAME::default();
+    request.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME;
+    request.header.size = std::mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>() as u32;
+    request.header.adapterId = path.targetInfo.adapterId;
+    request.header.id = path.targetInfo.id;
+
+    if unsafe { DisplayConfigGetDeviceInfo(&mut request.header) } != 0 {
+        return None;
+    }
+    Some(wide_to_string(&request.monitorFriendlyDeviceName))
+}
+
+/// What `EnumDisplaySettingsW` reports for a display's current mode
+struct CurrentSettings {
+    mode: DisplayMode,
+    /// Top-left corner on the virtual desktop; the primary display sits at the origin
+    position: POINT,
+    /// Desktop surface depth (32 for 8 bits per channel plus padding)
+    bits_per_pixel: u32,
+    orientation: DisplayOrientation,
+}
+
+fn current_settings(device_name: &str) -> Option<CurrentSettings> {
+    let device = to_wide(device_name);
+    let mut devmode = DEVMODEW {
+        dmSize: std::mem::size_of::<DEVMODEW>() as u16,
+        ..Default::default()
+    };
+
+    unsafe {
+        if !EnumDisplaySettingsW(PCWSTR(device.as_ptr()), ENUM_CURRENT_SETTINGS, &mut devmode).as_bool() {
+            return None;
+        }
+        let display_fields = devmode.Anonymous1.Anonymous2;
+        Some(CurrentSettings {
+            mode: DisplayMode {
+                width: devmode.dmPelsWidth,
+                height: devmode.dmPelsHeight,
+                refresh_hz: devmode.dmDisplayFrequency,
+            },
+            position: POINT {
+                x: display_fields.dmPosition.x,
+                y: display_fields.dmPosition.y,
+            },
+            bits_per_pixel: devmode.dmBitsPerPel,
+            orientation: match display_fields.dmDisplayOrientation {
+                DMDO_90 => DisplayOrientation::Portrait,
+                DMDO_180 => DisplayOrientation::LandscapeFlipped,
+                DMDO_270 => DisplayOrientation::PortraitFlipped,
+                _ => DisplayOrientation::Landscape,
+            },
+        })
+    }
+}
+
+/// Advanced color state of a path's target
+#[derive(Clone, Copy)]
+struct AdvancedColor {
+    supported: bool,
+    enabled: bool,
+    /// Bits per color channel on the link to the monitor
+    bits_per_channel: u32,
+}
+
+/// Query a target's advanced color info (Windows 10 1703+)
+fn advanced_color(path: &DISPLAYCONFIG_PATH_INFO) -> Option<AdvancedColor> {
+    let mut request = DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO::default();
+    request.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_ADVANCED_COLOR_INFO;
+    request.header.size = std::mem::size_of::<DISPLAYCONFIG_GET_ADVANCED_COLOR_INFO>() as u32;
+    request.header.adapterId = path.targetInfo.adapterId;
+    request.header.id = path.targetInfo.id;
+
+    if unsafe { DisplayConfigGetDeviceInfo(&mut request.header) } != 0 {
+        return None;
+    }
+    // Bit 0: advancedColorSupported, bit 1: advancedColorEnabled
+    let flags = unsafe { request.Anonymous.value };
+    Some(AdvancedColor {
+        supported: flags & 0x1 != 0,
+        enabled: flags & 0x2 != 0,
+        bits_per_channel: request.bitsPerColorChannel,
+    })
+}
+
+/// Effective scaling of the monitor containing a point, in percent
+fn scale_percent(position: POINT) -> u32 {
+    unsafe {
+        // DPI-unaware callers are always told 96 DPI
+        let previous = SetThreadDpiAwarenessContext(DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2);
+        let monitor = MonitorFromPoint(position, MONITOR_DEFAULTTONEAREST);
+        let (mut dpi_x, mut dpi_y) = (0u32, 0u32);
+        let result = GetDpiForMonitor(monitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y);
+        if !previous.is_invalid() {
+            SetThreadDpiAwarenessContext(previous);
+        }
+
+        match result {
+            Ok(()) if dpi_x > 0 => (dpi_x * 100 + 48) / 96,
+            _ => 100,
+        }
+    }
+}
+
+fn wide_to_string(buffer: &[u16]) -> String {
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    String::from_utf16_lossy(&buffer[..len])
+}
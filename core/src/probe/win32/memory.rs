@@ -0,0 +1,23 @@
+//! Win32 physical memory and commit charge
+
+use crate::probe::MemoryStatus;
+use windows::Win32::System::SystemInformation::{GlobalMemoryStatusEx, MEMORYSTATUSEX};
+
+/// Read memory figures with GlobalMemoryStatusEx
+///
+/// Its "page file" fields are the system commit limit and the commit still
+/// available, not the size of pagefile.sys.
+pub fn memory_status() -> Result<MemoryStatus, String> {
+    let mut status = MEMORYSTATUSEX {
+        dwLength: std::mem::size_of::<MEMORYSTATUSEX>() as u32,
+        ..Default::default()
+    };
+    unsafe { GlobalMemoryStatusEx(&mut status) }.map_err(|e| format!("Failed to read memory status ({})", e))?;
+
+    Ok(MemoryStatus {
+        total_bytes: status.ullTotalPhys,
+        available_bytes: status.ullAvailPhys,
+        commit_bytes: status.ullTotalPageFile.saturating_sub(status.ullAvailPageFile),
+        commit_limit_bytes: status.ullTotalPageFile,
+    })
+}
@@ -0,0 +1,182 @@
+//! Live Windows backend for [`SystemProbe`]
+
+mod display;
+mod memory;
+mod power;
+mod processes;
+mod registry;
+mod services;
+mod storage;
+mod tasks;
+mod token;
+
+use crate::probe::{
+    Chassis, CpuUsage, DiskSpace, DisplayInfo, DisplayMode, DriveKind, Guid, MemoryStatus, PowerSchemeInfo,
+    PowerSettingValues, PowerSource, PowerStatus, ProcessInfo, RegistryHive, RegistryValue, RegistryView,
+    ServiceStartType, ServiceStatus, SystemProbe, TaskStatus,
+};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Probe that reads and writes the state of the local Windows machine
+#[derive(Debug, Default)]
+pub struct Win32Probe {
+    /// Looked up on first use; the session's user can't change while we run in it
+    interactive_user_sid: OnceLock<Option<String>>,
+    cpu_sampler: processes::CpuSampler,
+}
+
+impl Win32Probe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Let `cpu_usage` wait for a full window of samples instead of reporting
+    /// that CPU load isn't measured yet, for callers that check only once
+    pub fn waiting_for_cpu_samples(self) -> Self {
+        Self {
+            cpu_sampler: processes::CpuSampler::waiting(),
+            ..self
+        }
+    }
+}
+
+impl SystemProbe for Win32Probe {
+    fn read_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<RegistryValue, String> {
+        registry::read_value(hive, view, subkey, value_name)
+    }
+
+    fn write_value(
+        &self,
+        hive: RegistryHive,
+        view: RegistryView,
+        subkey: &str,
+        value_name: &str,
+        value: &RegistryValue,
+    ) -> Result<(), String> {
+        registry::write_value(hive, view, subkey, value_name, value)
+    }
+
+    fn delete_value(&self, hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<(), String> {
+        registry::delete_value(hive, view, subkey, value_name)
+    }
+
+    fn interactive_user_sid(&self) -> Option<String> {
+        self.interactive_user_sid.get_or_init(token::interactive_user_sid).clone()
+    }
+
+    fn active_power_scheme(&self) -> Result<Guid, String> {
+        power::active_scheme()
+    }
+
+    fn set_active_power_scheme(&self, scheme: Guid) -> Result<(), String> {
+        power::set_active_scheme(scheme)
+    }
+
+    fn power_schemes(&self) -> Result<Vec<PowerSchemeInfo>, String> {
+        power::schemes()
+    }
+
+    fn duplicate_power_scheme(&self, source: Guid, copy: Guid) -> Result<(), String> {
+        power::duplicate_scheme(source, copy)
+    }
+
+    fn power_mode(&self) -> Result<Guid, String> {
+        power::active_overlay()
+    }
+
+    fn set_power_mode(&self, mode: Guid) -> Result<(), String> {
+        power::set_active_overlay(mode)
+    }
+
+    fn power_setting(&self, scheme: Guid, subgroup: Guid, setting: Guid) -> Result<PowerSettingValues, String> {
+        power::read_setting(scheme, subgroup, setting)
+    }
+
+    fn set_power_setting(
+        &self,
+        scheme: Guid,
+        subgroup: Guid,
+        setting: Guid,
+        source: PowerSource,
+        value: u32,
+    ) -> Result<(), String> {
+        power::write_setting(scheme, subgroup, setting, source, value)
+    }
+
+    fn running_processes(&self) -> Result<Vec<ProcessInfo>, String> {
+        processes::running_processes()
+    }
+
+    fn process_path(&self, pid: u32) -> Result<String, String> {
+        processes::image_path(pid)
+    }
+
+    fn terminate_process(&self, pid: u32) -> Result<(), String> {
+        processes::terminate(pid)
+    }
+
+    fn cpu_usage(&self, window: Duration) -> Result<CpuUsage, String> {
+        self.cpu_sampler.usage(window)
+    }
+
+    fn memory_status(&self) -> Result<MemoryStatus, String> {
+        memory::memory_status()
+    }
+
+    fn disk_space(&self, path: &str) -> Result<DiskSpace, String> {
+        storage::disk_space(path)
+    }
+
+    fn drive_kind(&self, path: &str) -> Result<DriveKind, String> {
+        storage::drive_kind(path)
+    }
+
+    fn displays(&self) -> Result<Vec<DisplayInfo>, String> {
+        display::displays()
+    }
+
+    fn set_hdr_enabled(&self, device_name: &str, enabled: bool) -> Result<(), String> {
+        display::set_hdr_enabled(device_name, enabled)
+    }
+
+    fn display_modes(&self, device_name: &str) -> Result<Vec<DisplayMode>, String> {
+        display::display_modes(device_name)
+    }
+
+    fn set_display_mode(&self, device_name: &str, mode: DisplayMode) -> Result<(), String> {
+        display::set_display_mode(device_name, mode)
+    }
+
+    fn service_status(&self, name: &str) -> Result<ServiceStatus, String> {
+        services::status(name)
+    }
+
+    fn set_service_running(&self, name: &str, running: bool) -> Result<(), String> {
+        services::set_running(name, running)
+    }
+
+    fn set_service_start_type(&self, name: &str, start_type: ServiceStartType) -> Result<(), String> {
+        services::set_start_type(name, start_type)
+    }
+
+    fn scheduled_task(&self, path: &str) -> Result<TaskStatus, String> {
+        tasks::status(path)
+    }
+
+    fn set_task_enabled(&self, path: &str, enabled: bool) -> Result<(), String> {
+        tasks::set_enabled(path, enabled)
+    }
+
+    fn stop_task(&self, path: &str) -> Result<(), String> {
+        tasks::stop(path)
+    }
+
+    fn power_status(&self) -> Result<PowerStatus, String> {
+        power::power_status()
+    }
+
+    fn chassis(&self) -> Result<Chassis, String> {
+        power::chassis()
+    }
+}
@@ -0,0 +1,278 @@
+//! Win32 power scheme, power mode (overlay) and battery access
+
+use crate::probe::{
+    Chassis, Guid, PowerSchemeInfo, PowerSettingValues, PowerSource, PowerStatus, POWER_SETTING_NOT_FOUND,
+};
+use std::sync::OnceLock;
+use windows::core::{GUID, PCWSTR};
+use windows::Win32::Foundation::{
+    ERROR_ACCESS_DENIED, ERROR_FILE_NOT_FOUND, ERROR_NO_MORE_ITEMS, ERROR_SUCCESS, HMODULE,
+};
+use windows::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryW};
+use windows::Win32::System::Power::{
+    GetPwrCapabilities, GetSystemPowerStatus, PowerDuplicateScheme, PowerEnumerate, PowerGetActiveScheme,
+    PowerReadACValueIndex, PowerReadDCValueIndex, PowerReadFriendlyName, PowerSetActiveScheme,
+    PowerWriteACValueIndex, PowerWriteDCValueIndex, ACCESS_SCHEME, SYSTEM_POWER_CAPABILITIES, SYSTEM_POWER_STATUS,
+};
+
+// Link to kernel32 for LocalFree
+#[link(name = "kernel32")]
+extern "system" {
+    fn LocalFree(hMem: *mut std::ffi::c_void) -> *mut std::ffi::c_void;
+}
+
+// Power overlay scheme functions - loaded dynamically since they're not in all SDK versions
+type PowerGetActualOverlaySchemeFn = unsafe extern "system" fn(*mut GUID) -> u32;
+type PowerSetActiveOverlaySchemeFn = unsafe extern "system" fn(*const GUID) -> u32;
+
+static POWER_OVERLAY_FUNCS: OnceLock<Option<(PowerGetActualOverlaySchemeFn, PowerSetActiveOverlaySchemeFn)>> = OnceLock::new();
+
+fn get_overlay_funcs() -> Option<(PowerGetActualOverlaySchemeFn, PowerSetActiveOverlaySchemeFn)> {
+    *POWER_OVERLAY_FUNCS.get_or_init(|| {
+        unsafe {
+            let lib_name: Vec<u16> = "powrprof.dll\0".encode_utf16().collect();
+            let lib = LoadLibraryW(PCWSTR::from_raw(lib_name.as_ptr())).ok()?;
+            if lib == HMODULE::default() {
+                return None;
+            }
+
+            let get_fn = GetProcAddress(lib, windows::core::s!("PowerGetActualOverlayScheme"))?;
+            let set_fn = GetProcAddress(lib, windows::core::s!("PowerSetActiveOverlayScheme"))?;
+
+            Some((
+                std::mem::transmute::<unsafe extern "system" fn() -> isize, PowerGetActualOverlaySchemeFn>(get_fn),
+                std::mem::transmute::<unsafe extern "system" fn() -> isize, PowerSetActiveOverlaySchemeFn>(set_fn),
+            ))
+        }
+    })
+}
+
+/// Get the GUID of the active power scheme
+pub fn active_scheme() -> Result<Guid, String> {
+    unsafe {
+        let mut scheme_guid: *mut GUID = std::ptr::null_mut();
+
+        let result = PowerGetActiveScheme(None, &mut scheme_guid);
+
+        if result.is_err() {
+            return Err(format!("Failed to get active power scheme: {:?}", result));
+        }
+
+        if scheme_guid.is_null() {
+            return Err("PowerGetActiveScheme returned null".to_string());
+        }
+
+        let current_guid = *scheme_guid;
+
+        // Free the allocated GUID - Windows allocated this memory
+        LocalFree(scheme_guid as *mut _);
+
+        Ok(Guid::from_u128(current_guid.to_u128()))
+    }
+}
+
+/// List installed power schemes with their friendly names
+pub fn schemes() -> Result<Vec<PowerSchemeInfo>, String> {
+    let mut schemes = Vec::new();
+
+    for index in 0.. {
+        let mut guid = GUID::zeroed();
+        let mut size = std::mem::size_of::<GUID>() as u32;
+        let result = unsafe {
+            PowerEnumerate(None, None, None, ACCESS_SCHEME, index, Some(&mut guid as *mut GUID as *mut u8), &mut size)
+        };
+
+        if result == ERROR_NO_MORE_ITEMS {
+            break;
+        }
+        if result != ERROR_SUCCESS {
+            return Err(format!("Failed to list power schemes (error {})", result.0));
+        }
+
+        schemes.push(PowerSchemeInfo {
+            guid: Guid::from_u128(guid.to_u128()),
+            name: friendly_name(&guid).unwrap_or_default(),
+        });
+    }
+
+    Ok(schemes)
+}
+
+/// Friendly name of a scheme
+fn friendly_name(scheme: &GUID) -> Option<String> {
+    unsafe {
+        // First call only reports the size needed (in bytes, including the NUL)
+        let mut size = 0u32;
+        let _ = PowerReadFriendlyName(None, Some(scheme), None, None, None, &mut size);
+        if size == 0 {
+            return None;
+        }
+
+        let mut buffer = vec![0u16; (size as usize).div_ceil(2)];
+        let result = PowerReadFriendlyName(None, Some(scheme), None, None, Some(buffer.as_mut_ptr() as *mut u8), &mut size);
+        if result != ERROR_SUCCESS {
+            return None;
+        }
+
+        let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+        Some(String::from_utf16_lossy(&buffer[..len]))
+    }
+}
+
+/// Copy a scheme (or a hidden built-in template) under the GUID `copy`
+pub fn duplicate_scheme(source: Guid, copy: Guid) -> Result<(), String> {
+    let source = GUID::from_u128(source.to_u128());
+    let mut copy = GUID::from_u128(copy.to_u128());
+
+    // A non-null destination makes Windows use our GUID rather than allocate one
+    let mut destination: *mut GUID = &mut copy;
+    let result = unsafe { PowerDuplicateScheme(None, &source, &mut destination) };
+    if result != ERROR_SUCCESS {
+        return Err(format!("Failed to duplicate power scheme (error {})", result.0));
+    }
+    Ok(())
+}
+
+/// Set the active power scheme
+pub fn set_active_scheme(scheme: Guid) -> Result<(), String> {
+    let guid = GUID::from_u128(scheme.to_u128());
+
+    unsafe {
+        let result = PowerSetActiveScheme(None, Some(&guid));
+        if result == ERROR_SUCCESS {
+            Ok(())
+        } else {
+            Err(format!("Failed to set power scheme (error {})", result.0))
+        }
+    }
+}
+
+/// Get the GUID of the active power mode overlay
+pub fn active_overlay() -> Result<Guid, String> {
+    let Some((get_fn, _)) = get_overlay_funcs() else {
+        return Err("Power mode API not available on this Windows version".to_string());
+    };
+
+    unsafe {
+        let mut mode_guid: GUID = GUID::from_u128(0);
+
+        let result = get_fn(&mut mode_guid);
+
+        if result != 0 {
+            return Err(format!("Failed to get power mode: error {}", result));
+        }
+
+        Ok(Guid::from_u128(mode_guid.to_u128()))
+    }
+}
+
+/// Set the active power mode overlay
+pub fn set_active_overlay(mode: Guid) -> Result<(), String> {
+    let Some((_, set_fn)) = get_overlay_funcs() else {
+        return Err("Power mode API not available on this Windows version".to_string());
+    };
+
+    let guid = GUID::from_u128(mode.to_u128());
+
+    unsafe {
+        let result = set_fn(&guid);
+        if result == 0 {
+            Ok(())
+        } else {
+            Err(format!("Failed to set power mode (error {})", result))
+        }
+    }
+}
+
+/// Read the AC and DC value indices of a setting in a scheme
+pub fn read_setting(scheme: Guid, subgroup: Guid, setting: Guid) -> Result<PowerSettingValues, String> {
+    let scheme = GUID::from_u128(scheme.to_u128());
+    let subgroup = GUID::from_u128(subgroup.to_u128());
+    let setting = GUID::from_u128(setting.to_u128());
+
+    let (mut ac, mut dc) = (0u32, 0u32);
+    unsafe {
+        let ac_result = PowerReadACValueIndex(None, Some(&scheme), Some(&subgroup), Some(&setting), &mut ac).0;
+        let dc_result = PowerReadDCValueIndex(None, Some(&scheme), Some(&subgroup), Some(&setting), &mut dc);
+
+        for result in [ac_result, dc_result] {
+            if result == ERROR_FILE_NOT_FOUND.0 {
+                return Err(POWER_SETTING_NOT_FOUND.to_string());
+            }
+            if result != ERROR_SUCCESS.0 {
+                return Err(format!("Failed to read power setting (error {})", result));
+            }
+        }
+    }
+
+    Ok(PowerSettingValues { ac, dc })
+}
+
+/// Write one value index of a setting, re-applying the scheme if it is active
+/// (Windows only picks up changes to the active scheme when it is set again)
+pub fn write_setting(scheme: Guid, subgroup: Guid, setting: Guid, source: PowerSource, value: u32) -> Result<(), String> {
+    let is_active = active_scheme().is_ok_and(|active| active == scheme);
+    let scheme = GUID::from_u128(scheme.to_u128());
+    let subgroup = GUID::from_u128(subgroup.to_u128());
+    let setting = GUID::from_u128(setting.to_u128());
+
+    let result = unsafe {
+        match source {
+            PowerSource::Ac => PowerWriteACValueIndex(None, &scheme, Some(&subgroup), Some(&setting), value).0,
+            PowerSource::Dc => PowerWriteDCValueIndex(None, &scheme, Some(&subgroup), Some(&setting), value),
+        }
+    };
+
+    if result == ERROR_FILE_NOT_FOUND.0 {
+        return Err(POWER_SETTING_NOT_FOUND.to_string());
+    }
+    if result == ERROR_ACCESS_DENIED.0 {
+        return Err("Access denied - admin required".to_string());
+    }
+    if result != ERROR_SUCCESS.0 {
+        return Err(format!("Failed to write power setting (error {})", result));
+    }
+
+    if is_active {
+        unsafe {
+            let result = PowerSetActiveScheme(None, Some(&scheme));
+            if result != ERROR_SUCCESS {
+                return Err(format!("Failed to re-apply power scheme (error {})", result.0));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// AC line status and battery charge
+pub fn power_status() -> Result<PowerStatus, String> {
+    let mut status = SYSTEM_POWER_STATUS::default();
+    unsafe { GetSystemPowerStatus(&mut status) }.map_err(|e| format!("Failed to get power status: {}", e))?;
+
+    // BatteryFlag 128 = no system battery, 255 and percent 255 = unknown
+    let has_battery = status.BatteryFlag & 128 == 0 && status.BatteryFlag != 255;
+    Ok(PowerStatus {
+        ac_online: match status.ACLineStatus {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        },
+        battery_percent: (has_battery && status.BatteryLifePercent <= 100).then_some(status.BatteryLifePercent),
+    })
+}
+
+/// Laptop if the machine has a lid or an internal (not UPS) battery
+pub fn chassis() -> Result<Chassis, String> {
+    let mut capabilities = SYSTEM_POWER_CAPABILITIES::default();
+    if !unsafe { GetPwrCapabilities(&mut capabilities) }.as_bool() {
+        return Err("Failed to get power capabilities".to_string());
+    }
+
+    let internal_battery =
+        capabilities.SystemBatteriesPresent.as_bool() && !capabilities.BatteriesAreShortTerm.as_bool();
+    if capabilities.LidPresent.as_bool() || internal_battery {
+        Ok(Chassis::Laptop)
+    } else {
+        Ok(Chassis::Desktop)
+    }
+}
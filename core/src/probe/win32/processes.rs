@@ -0,0 +1,309 @@
+//! Win32 process enumeration, termination and CPU sampling
+
+use crate::probe::{CpuUsage, ProcessCpu, ProcessInfo, CPU_NOT_SAMPLED};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+use std::time::{Duration, Instant};
+use windows::core::PWSTR;
+use windows::Wdk::System::SystemInformation::{NtQuerySystemInformation, SystemProcessInformation};
+use windows::Win32::Foundation::{CloseHandle, FILETIME, HANDLE, STATUS_INFO_LENGTH_MISMATCH};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, Process32FirstW, Process32NextW, PROCESSENTRY32W, TH32CS_SNAPPROCESS,
+};
+use windows::Win32::System::Threading::{
+    GetSystemTimes, OpenProcess, QueryFullProcessImageNameW, TerminateProcess, PROCESS_NAME_WIN32,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_TERMINATE,
+};
+use windows::Win32::System::WindowsProgramming::SYSTEM_PROCESS_INFORMATION;
+
+/// List all running processes from a single toolhelp snapshot, without
+/// opening any of them
+pub fn running_processes() -> Result<Vec<ProcessInfo>, String> {
+    Ok(process_entries()?
+        .into_iter()
+        .map(|(pid, name)| ProcessInfo { pid, name })
+        .collect())
+}
+
+/// PID and executable name of every process in a toolhelp snapshot
+/// (the System Idle Process, PID 0, is left out)
+pub fn process_entries() -> Result<Vec<(u32, String)>, String> {
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0)
+            .map_err(|e| format!("Failed to snapshot processes ({})", e))?;
+
+        let mut entry = PROCESSENTRY32W {
+            dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+            ..Default::default()
+        };
+        let mut processes = Vec::new();
+
+        let mut more = Process32FirstW(snapshot, &mut entry).is_ok();
+        while more {
+            let pid = entry.th32ProcessID;
+            if pid != 0 {
+                let len = entry.szExeFile.iter().position(|&c| c == 0).unwrap_or(entry.szExeFile.len());
+                processes.push((pid, String::from_utf16_lossy(&entry.szExeFile[..len])));
+            }
+            more = Process32NextW(snapshot, &mut entry).is_ok();
+        }
+
+        let _ = CloseHandle(snapshot);
+        Ok(processes)
+    }
+}
+
+/// Full image path of a process (protected and system processes can't be queried)
+pub fn image_path(pid: u32) -> Result<String, String> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid)
+            .map_err(|e| format!("Failed to open process {} ({})", pid, e))?;
+        if handle == HANDLE::default() {
+            return Err(format!("Failed to open process {}", pid));
+        }
+
+        // Long paths can exceed MAX_PATH
+        let mut path_buffer = vec![0u16; 1024];
+        let mut size = path_buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(
+            handle,
+            PROCESS_NAME_WIN32,
+            PWSTR::from_raw(path_buffer.as_mut_ptr()),
+            &mut size,
+        );
+        let _ = CloseHandle(handle);
+
+        result.map_err(|e| format!("Failed to query the image path of process {} ({})", pid, e))?;
+        Ok(String::from_utf16_lossy(&path_buffer[..size as usize]))
+    }
+}
+
+/// Terminate a process by PID
+pub fn terminate(pid: u32) -> Result<(), String> {
+    unsafe {
+        let handle = OpenProcess(PROCESS_TERMINATE, false, pid)
+            .map_err(|e| format!("Failed to open process {} ({})", pid, e))?;
+
+        if handle == HANDLE::default() {
+            return Err(format!("Failed to open process {}", pid));
+        }
+
+        let result = TerminateProcess(handle, 0);
+        let _ = CloseHandle(handle);
+
+        result.map_err(|e| format!("Failed to terminate process {} ({})", pid, e))
+    }
+}
+
+/// Name and kernel + user CPU time of each process, keyed by PID and
+/// creation time so a reused PID isn't mistaken for the same process
+type ProcessTimes = HashMap<(u32, u64), (String, u64)>;
+
+/// System and per-process CPU times at one moment
+#[derive(Debug)]
+struct CpuSample {
+    taken: Instant,
+    idle: u64,
+    total: u64,
+    processes: ProcessTimes,
+}
+
+impl CpuSample {
+    fn take() -> Result<Self, String> {
+        let (idle, total) = system_times()?;
+        Ok(Self {
+            taken: Instant::now(),
+            idle,
+            total,
+            processes: process_times()?,
+        })
+    }
+
+    /// Utilization between an earlier sample and this one
+    ///
+    /// Percentages are of the total time of all logical processors, so a
+    /// process saturating one core of eight shows as 12.5%. Processes are
+    /// matched by PID and creation time; ones started in between count all
+    /// their CPU time, and ones that exited are missed.
+    fn usage_since(&self, before: &CpuSample) -> Result<CpuUsage, String> {
+        let elapsed = self.total.saturating_sub(before.total);
+        if elapsed == 0 {
+            return Err("CPU time did not advance while sampling".to_string());
+        }
+        let idle = self.idle.saturating_sub(before.idle);
+        let percent = |time: u64| 100.0 * time as f64 / elapsed as f64;
+
+        let mut processes: Vec<ProcessCpu> = self
+            .processes
+            .iter()
+            .filter_map(|(key, (name, time))| {
+                let used = time.saturating_sub(before.processes.get(key).map_or(0, |(_, t)| *t));
+                (used > 0).then(|| ProcessCpu {
+                    pid: key.0,
+                    name: name.clone(),
+                    percent: percent(used),
+                })
+            })
+            .collect();
+        processes.sort_by(|a, b| b.percent.total_cmp(&a.percent));
+
+        Ok(CpuUsage {
+            busy_percent: percent(elapsed.saturating_sub(idle)),
+            processes,
+        })
+    }
+}
+
+/// How often the background thread samples CPU times
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How much sample history to keep: the longest sample window plus slack
+const SAMPLE_HISTORY: Duration = Duration::from_secs(32);
+
+/// Measures CPU utilization from samples a background thread takes every
+/// [`SAMPLE_INTERVAL`], so checks never wait out their window
+///
+/// The thread starts on the first call and stops when the sampler is dropped.
+#[derive(Debug, Default)]
+pub struct CpuSampler {
+    history: Arc<Mutex<VecDeque<CpuSample>>>,
+    started: OnceLock<()>,
+    /// Wait for a full window of history instead of reporting [`CPU_NOT_SAMPLED`]
+    wait_for_window: bool,
+}
+
+impl CpuSampler {
+    /// A sampler whose first calls wait until the history covers their window
+    /// (for the command line, which checks once right after starting)
+    pub fn waiting() -> Self {
+        Self {
+            wait_for_window: true,
+            ..Self::default()
+        }
+    }
+
+    /// Utilization between the latest sample and the newest one at least
+    /// `window` older, or [`CPU_NOT_SAMPLED`] until the history is that long
+    pub fn usage(&self, window: Duration) -> Result<CpuUsage, String> {
+        self.started.get_or_init(|| spawn_sampling(Arc::downgrade(&self.history)));
+        let deadline = Instant::now() + window + 2 * SAMPLE_INTERVAL;
+
+        loop {
+            {
+                let history = self.history.lock().unwrap();
+                if let Some(latest) = history.back() {
+                    let before = history
+                        .iter()
+                        .rev()
+                        .find(|sample| latest.taken.duration_since(sample.taken) >= window);
+                    if let Some(before) = before {
+                        return latest.usage_since(before);
+                    }
+                }
+            }
+            if !self.wait_for_window || Instant::now() >= deadline {
+                return Err(CPU_NOT_SAMPLED.to_string());
+            }
+            std::thread::sleep(SAMPLE_INTERVAL);
+        }
+    }
+}
+
+/// Sample CPU times into `history` until its sampler is dropped
+fn spawn_sampling(history: Weak<Mutex<VecDeque<CpuSample>>>) {
+    std::thread::spawn(move || loop {
+        let sample = CpuSample::take();
+        let Some(shared) = history.upgrade() else {
+            return;
+        };
+        match sample {
+            Ok(sample) => {
+                let mut samples = shared.lock().unwrap();
+                samples.push_back(sample);
+                while samples.front().is_some_and(|s| s.taken.elapsed() > SAMPLE_HISTORY) {
+                    samples.pop_front();
+                }
+            }
+            Err(e) => tracing::warn!("CPU sample failed: {}", e),
+        }
+        // Don't keep the sampler's history alive while sleeping
+        drop(shared);
+        std::thread::sleep(SAMPLE_INTERVAL);
+    });
+}
+
+/// Idle and total (kernel + user, which includes idle) time of all processors
+fn system_times() -> Result<(u64, u64), String> {
+    let (mut idle, mut kernel, mut user) = (FILETIME::default(), FILETIME::default(), FILETIME::default());
+    unsafe { GetSystemTimes(Some(&mut idle), Some(&mut kernel), Some(&mut user)) }
+        .map_err(|e| format!("Failed to read system CPU times ({})", e))?;
+    Ok((ticks(idle), ticks(kernel) + ticks(user)))
+}
+
+/// Byte offsets of the creation, user and kernel times inside
+/// `SYSTEM_PROCESS_INFORMATION::Reserved1`
+const CREATE_TIME_OFFSET: usize = 24;
+const USER_TIME_OFFSET: usize = 32;
+const KERNEL_TIME_OFFSET: usize = 40;
+
+/// CPU times of every process from a single `SystemProcessInformation`
+/// query, without opening any of them
+fn process_times() -> Result<ProcessTimes, String> {
+    // u64 elements keep the entries 8-byte aligned
+    let mut buffer: Vec<u64> = vec![0; 32 * 1024];
+    loop {
+        let mut needed = 0u32;
+        let status = unsafe {
+            NtQuerySystemInformation(
+                SystemProcessInformation,
+                buffer.as_mut_ptr().cast(),
+                (buffer.len() * 8) as u32,
+                &mut needed,
+            )
+        };
+        if status == STATUS_INFO_LENGTH_MISMATCH {
+            // Leave room for processes that start before the next attempt
+            buffer.resize(needed as usize / 8 + 8 * 1024, 0);
+            continue;
+        }
+        if status.0 < 0 {
+            return Err(format!("Failed to query process times (NTSTATUS {:#x})", status.0));
+        }
+        break;
+    }
+
+    let mut times = HashMap::new();
+    let base = buffer.as_ptr().cast::<u8>();
+    let mut offset = 0;
+    loop {
+        let entry = unsafe { &*base.add(offset).cast::<SYSTEM_PROCESS_INFORMATION>() };
+        let time = |at: usize| u64::from_ne_bytes(entry.Reserved1[at..at + 8].try_into().unwrap());
+
+        // The System Idle Process (PID 0) is the idle time, not a process
+        let pid = entry.UniqueProcessId.0 as usize as u32;
+        if pid != 0 {
+            let name = if entry.ImageName.Buffer.is_null() {
+                String::new()
+            } else {
+                let len = usize::from(entry.ImageName.Length) / 2;
+                String::from_utf16_lossy(unsafe { std::slice::from_raw_parts(entry.ImageName.Buffer.0, len) })
+            };
+            times.insert(
+                (pid, time(CREATE_TIME_OFFSET)),
+                (name, time(USER_TIME_OFFSET) + time(KERNEL_TIME_OFFSET)),
+            );
+        }
+
+        if entry.NextEntryOffset == 0 {
+            break;
+        }
+        offset += entry.NextEntryOffset as usize;
+    }
+
+    Ok(times)
+}
+
+/// FILETIME as a count of 100ns ticks
+fn ticks(time: FILETIME) -> u64 {
+    (u64::from(time.dwHighDateTime) << 32) | u64::from(time.dwLowDateTime)
+}
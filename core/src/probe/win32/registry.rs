@@ -0,0 +1,271 @@
+//! Win32 registry access
+
+use crate::probe::{RegistryHive, RegistryValue, RegistryView, KEY_NOT_FOUND, VALUE_NOT_FOUND};
+use std::ptr;
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{ERROR_ACCESS_DENIED, ERROR_FILE_NOT_FOUND, ERROR_SUCCESS};
+use windows::Win32::System::Registry::{
+    RegCloseKey, RegDeleteValueW, RegOpenKeyExW, RegQueryValueExW, RegSetValueExW, HKEY,
+    HKEY_CLASSES_ROOT, HKEY_CURRENT_CONFIG, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, HKEY_USERS,
+    KEY_READ, KEY_WOW64_32KEY, KEY_WOW64_64KEY, KEY_WRITE, REG_BINARY, REG_DWORD, REG_EXPAND_SZ,
+    REG_MULTI_SZ, REG_QWORD, REG_SAM_FLAGS, REG_SZ, REG_VALUE_TYPE,
+};
+
+/// Convert a Rust string to a wide string (UTF-16)
+pub fn to_wide(s: &str) -> Vec<u16> {
+    s.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Map a hive to its predefined root key handle
+fn root_key(hive: RegistryHive) -> HKEY {
+    match hive {
+        RegistryHive::CurrentUser => HKEY_CURRENT_USER,
+        RegistryHive::LocalMachine => HKEY_LOCAL_MACHINE,
+        RegistryHive::Users => HKEY_USERS,
+        RegistryHive::ClassesRoot => HKEY_CLASSES_ROOT,
+        RegistryHive::CurrentConfig => HKEY_CURRENT_CONFIG,
+    }
+}
+
+/// Access flags selecting the 32- or 64-bit registry view
+fn view_flags(view: RegistryView) -> REG_SAM_FLAGS {
+    match view {
+        RegistryView::Native => REG_SAM_FLAGS(0),
+        RegistryView::Bit32 => KEY_WOW64_32KEY,
+        RegistryView::Bit64 => KEY_WOW64_64KEY,
+    }
+}
+
+/// Read a value of any supported type from the registry
+pub fn read_value(hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<RegistryValue, String> {
+    let subkey_wide = to_wide(subkey);
+    let value_wide = to_wide(value_name);
+
+    unsafe {
+        let mut hkey = HKEY::default();
+
+        let result = RegOpenKeyExW(
+            root_key(hive),
+            PCWSTR::from_raw(subkey_wide.as_ptr()),
+            0,
+            KEY_READ | view_flags(view),
+            &mut hkey,
+        );
+
+        if result == ERROR_FILE_NOT_FOUND {
+            return Err(KEY_NOT_FOUND.to_string());
+        } else if result == ERROR_ACCESS_DENIED {
+            return Err("Access denied (run as admin?)".to_string());
+        } else if result != ERROR_SUCCESS {
+            return Err(format!("Failed to open key (error {})", result.0));
+        }
+
+        // First, get the type and size needed
+        let mut data_size: u32 = 0;
+        let mut value_type = REG_VALUE_TYPE::default();
+
+        let result = RegQueryValueExW(
+            hkey,
+            PCWSTR::from_raw(value_wide.as_ptr()),
+            Some(ptr::null()),
+            Some(&mut value_type),
+            None,
+            Some(&mut data_size),
+        );
+
+        if result == ERROR_FILE_NOT_FOUND {
+            let _ = RegCloseKey(hkey);
+            return Err(VALUE_NOT_FOUND.to_string());
+        } else if result != ERROR_SUCCESS {
+            let _ = RegCloseKey(hkey);
+            return Err(format!("Failed to query value (error {})", result.0));
+        }
+
+        // Allocate buffer and read the value
+        let mut buffer: Vec<u8> = vec![0; data_size as usize];
+
+        let result = RegQueryValueExW(
+            hkey,
+            PCWSTR::from_raw(value_wide.as_ptr()),
+            Some(ptr::null()),
+            Some(&mut value_type),
+            Some(buffer.as_mut_ptr()),
+            Some(&mut data_size),
+        );
+
+        let _ = RegCloseKey(hkey);
+
+        if result != ERROR_SUCCESS {
+            return Err(format!("Failed to read value (error {})", result.0));
+        }
+
+        buffer.truncate(data_size as usize);
+        decode_value(value_type, &buffer)
+    }
+}
+
+/// Convert raw registry data to a typed value
+fn decode_value(value_type: REG_VALUE_TYPE, data: &[u8]) -> Result<RegistryValue, String> {
+    match value_type {
+        REG_DWORD => data
+            .get(..4)
+            .map(|b| RegistryValue::Dword(u32::from_le_bytes([b[0], b[1], b[2], b[3]])))
+            .ok_or_else(|| "REG_DWORD value is truncated".to_string()),
+        REG_QWORD => data
+            .get(..8)
+            .map(|b| {
+                let mut bytes = [0u8; 8];
+                bytes.copy_from_slice(b);
+                RegistryValue::Qword(u64::from_le_bytes(bytes))
+            })
+            .ok_or_else(|| "REG_QWORD value is truncated".to_string()),
+        REG_SZ => decode_wide(data).map(RegistryValue::String),
+        REG_EXPAND_SZ => decode_wide(data).map(RegistryValue::ExpandString),
+        REG_MULTI_SZ => {
+            let text = decode_wide_all(data)?;
+            Ok(RegistryValue::MultiString(
+                text.split('\0')
+                    .filter(|s| !s.is_empty())
+                    .map(|s| s.to_string())
+                    .collect(),
+            ))
+        }
+        REG_BINARY => Ok(RegistryValue::Binary(data.to_vec())),
+        other => Err(format!("Unsupported registry value type {}", other.0)),
+    }
+}
+
+/// Decode UTF-16LE data up to the first null terminator
+fn decode_wide(data: &[u8]) -> Result<String, String> {
+    let text = decode_wide_all(data)?;
+    Ok(text.split('\0').next().unwrap_or_default().to_string())
+}
+
+/// Decode all UTF-16LE data, keeping embedded nulls
+fn decode_wide_all(data: &[u8]) -> Result<String, String> {
+    let wide: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect();
+
+    String::from_utf16(&wide).map_err(|e| format!("Failed to decode string: {}", e))
+}
+
+/// Encode a string as null-terminated UTF-16LE bytes
+fn encode_wide(s: &str) -> Vec<u8> {
+    to_wide(s).iter().flat_map(|c| c.to_le_bytes()).collect()
+}
+
+/// Write a value of any supported type to the registry
+pub fn write_value(
+    hive: RegistryHive,
+    view: RegistryView,
+    subkey: &str,
+    value_name: &str,
+    value: &RegistryValue,
+) -> Result<(), String> {
+    let write = |value_type: REG_VALUE_TYPE, data: &[u8]| {
+        write_raw(hive, view, subkey, value_name, value_type, data)
+    };
+
+    match value {
+        RegistryValue::Dword(v) => write(REG_DWORD, &v.to_le_bytes()),
+        RegistryValue::Qword(v) => write(REG_QWORD, &v.to_le_bytes()),
+        RegistryValue::String(s) => write(REG_SZ, &encode_wide(s)),
+        RegistryValue::ExpandString(s) => write(REG_EXPAND_SZ, &encode_wide(s)),
+        RegistryValue::MultiString(entries) => {
+            // Each entry null-terminated, then an empty string ends the list
+            let mut data: Vec<u8> = entries.iter().flat_map(|s| encode_wide(s)).collect();
+            data.extend_from_slice(&[0, 0]);
+            write(REG_MULTI_SZ, &data)
+        }
+        RegistryValue::Binary(bytes) => write(REG_BINARY, bytes),
+    }
+}
+
+/// Open an existing key for writing and set a value of the given type
+fn write_raw(
+    hive: RegistryHive,
+    view: RegistryView,
+    subkey: &str,
+    value_name: &str,
+    value_type: REG_VALUE_TYPE,
+    data: &[u8],
+) -> Result<(), String> {
+    let subkey_wide = to_wide(subkey);
+    let value_wide = to_wide(value_name);
+
+    unsafe {
+        let mut hkey = HKEY::default();
+
+        let result = RegOpenKeyExW(
+            root_key(hive),
+            PCWSTR::from_raw(subkey_wide.as_ptr()),
+            0,
+            KEY_WRITE | view_flags(view),
+            &mut hkey,
+        );
+
+        if result == ERROR_FILE_NOT_FOUND {
+            return Err(KEY_NOT_FOUND.to_string());
+        } else if result == ERROR_ACCESS_DENIED {
+            return Err("Access denied - admin required".to_string());
+        } else if result != ERROR_SUCCESS {
+            return Err(format!("Failed to open key (error {})", result.0));
+        }
+
+        let result = RegSetValueExW(
+            hkey,
+            PCWSTR::from_raw(value_wide.as_ptr()),
+            0,
+            value_type,
+            Some(data),
+        );
+
+        let _ = RegCloseKey(hkey);
+
+        if result != ERROR_SUCCESS {
+            return Err(format!("Failed to write value (error {})", result.0));
+        }
+
+        Ok(())
+    }
+}
+
+/// Delete a value from an existing key
+pub fn delete_value(hive: RegistryHive, view: RegistryView, subkey: &str, value_name: &str) -> Result<(), String> {
+    let subkey_wide = to_wide(subkey);
+    let value_wide = to_wide(value_name);
+
+    unsafe {
+        let mut hkey = HKEY::default();
+
+        let result = RegOpenKeyExW(
+            root_key(hive),
+            PCWSTR::from_raw(subkey_wide.as_ptr()),
+            0,
+            KEY_WRITE | view_flags(view),
+            &mut hkey,
+        );
+
+        if result == ERROR_FILE_NOT_FOUND {
+            return Err(KEY_NOT_FOUND.to_string());
+        } else if result == ERROR_ACCESS_DENIED {
+            return Err("Access denied - admin required".to_string());
+        } else if result != ERROR_SUCCESS {
+            return Err(format!("Failed to open key (error {})", result.0));
+        }
+
+        let result = RegDeleteValueW(hkey, PCWSTR::from_raw(value_wide.as_ptr()));
+
+        let _ = RegCloseKey(hkey);
+
+        if result == ERROR_FILE_NOT_FOUND {
+            return Err(VALUE_NOT_FOUND.to_string());
+        } else if result != ERROR_SUCCESS {
+            return Err(format!("Failed to delete value (error {})", result.0));
+        }
+
+        Ok(())
+    }
+}
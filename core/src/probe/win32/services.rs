@@ -0,0 +1,174 @@
+//! Win32 service control manager access
+
+use crate::probe::win32::registry::to_wide;
+use crate::probe::{ServiceStartType, ServiceState, ServiceStatus, SERVICE_NOT_FOUND};
+use std::thread;
+use std::time::{Duration, Instant};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{
+    ERROR_ACCESS_DENIED, ERROR_SERVICE_ALREADY_RUNNING, ERROR_SERVICE_DOES_NOT_EXIST,
+    ERROR_SERVICE_NOT_ACTIVE,
+};
+use windows::Win32::System::Services::{
+    ChangeServiceConfigW, CloseServiceHandle, ControlService, OpenSCManagerW, OpenServiceW,
+    QueryServiceConfigW, QueryServiceStatus, StartServiceW, ENUM_SERVICE_TYPE, QUERY_SERVICE_CONFIGW,
+    SC_HANDLE, SC_MANAGER_CONNECT, SERVICE_AUTO_START, SERVICE_BOOT_START, SERVICE_CHANGE_CONFIG,
+    SERVICE_CONTROL_STOP, SERVICE_DEMAND_START, SERVICE_DISABLED, SERVICE_ERROR, SERVICE_NO_CHANGE,
+    SERVICE_PAUSED, SERVICE_QUERY_CONFIG, SERVICE_QUERY_STATUS, SERVICE_RUNNING, SERVICE_START,
+    SERVICE_STATUS, SERVICE_STOP, SERVICE_STOPPED, SERVICE_SYSTEM_START,
+};
+
+/// How long to wait for a service to start or stop
+const STATE_CHANGE_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Service handle closed (together with its manager) on drop
+struct Service {
+    manager: SC_HANDLE,
+    handle: SC_HANDLE,
+}
+
+impl Service {
+    fn open(name: &str, access: u32) -> Result<Self, String> {
+        let name_wide = to_wide(name);
+
+        unsafe {
+            let manager = OpenSCManagerW(PCWSTR::null(), PCWSTR::null(), SC_MANAGER_CONNECT)
+                .map_err(|e| format!("Failed to open service manager ({})", e))?;
+
+            match OpenServiceW(manager, PCWSTR::from_raw(name_wide.as_ptr()), access) {
+                Ok(handle) => Ok(Self { manager, handle }),
+                Err(e) => {
+                    let _ = CloseServiceHandle(manager);
+                    if e.code() == ERROR_SERVICE_DOES_NOT_EXIST.to_hresult() {
+                        Err(SERVICE_NOT_FOUND.to_string())
+                    } else if e.code() == ERROR_ACCESS_DENIED.to_hresult() {
+                        Err("Access denied - admin required".to_string())
+                    } else {
+                        Err(format!("Failed to open service {} ({})", name, e))
+                    }
+                }
+            }
+        }
+    }
+
+    fn state(&self) -> Result<ServiceState, String> {
+        let mut status = SERVICE_STATUS::default();
+        unsafe { QueryServiceStatus(self.handle, &mut status) }
+            .map_err(|e| format!("Failed to query service status ({})", e))?;
+
+        Ok(match status.dwCurrentState {
+            SERVICE_RUNNING => ServiceState::Running,
+            SERVICE_STOPPED => ServiceState::Stopped,
+            SERVICE_PAUSED => ServiceState::Paused,
+            _ => ServiceState::Pending,
+        })
+    }
+
+    fn start_type(&self) -> Result<ServiceStartType, String> {
+        unsafe {
+            // First call only reports the size needed
+            let mut needed = 0u32;
+            let _ = QueryServiceConfigW(self.handle, None, 0, &mut needed);
+
+            // u64 storage keeps QUERY_SERVICE_CONFIGW aligned
+            let mut buffer = vec![0u64; (needed as usize).div_ceil(8).max(1)];
+            let config = buffer.as_mut_ptr() as *mut QUERY_SERVICE_CONFIGW;
+            QueryServiceConfigW(self.handle, Some(config), (buffer.len() * 8) as u32, &mut needed)
+                .map_err(|e| format!("Failed to query service config ({})", e))?;
+
+            match (*config).dwStartType {
+                SERVICE_BOOT_START => Ok(ServiceStartType::Boot),
+                SERVICE_SYSTEM_START => Ok(ServiceStartType::System),
+                SERVICE_AUTO_START => Ok(ServiceStartType::Automatic),
+                SERVICE_DEMAND_START => Ok(ServiceStartType::Manual),
+                SERVICE_DISABLED => Ok(ServiceStartType::Disabled),
+                other => Err(format!("Unknown service start type {}", other.0)),
+            }
+        }
+    }
+}
+
+impl Drop for Service {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseServiceHandle(self.handle);
+            let _ = CloseServiceHandle(self.manager);
+        }
+    }
+}
+
+/// State and start type of a service
+pub fn status(name: &str) -> Result<ServiceStatus, String> {
+    let service = Service::open(name, SERVICE_QUERY_STATUS | SERVICE_QUERY_CONFIG)?;
+
+    Ok(ServiceStatus {
+        state: service.state()?,
+        start_type: service.start_type()?,
+    })
+}
+
+/// Start or stop a service and wait for it to get there
+pub fn set_running(name: &str, running: bool) -> Result<(), String> {
+    let access = SERVICE_QUERY_STATUS | if running { SERVICE_START } else { SERVICE_STOP };
+    let service = Service::open(name, access)?;
+
+    let result = unsafe {
+        if running {
+            StartServiceW(service.handle, None)
+        } else {
+            let mut status = SERVICE_STATUS::default();
+            ControlService(service.handle, SERVICE_CONTROL_STOP, &mut status)
+        }
+    };
+
+    if let Err(e) = result {
+        let already = if running { ERROR_SERVICE_ALREADY_RUNNING } else { ERROR_SERVICE_NOT_ACTIVE };
+        if e.code() != already.to_hresult() {
+            let action = if running { "start" } else { "stop" };
+            return Err(format!("Failed to {} {} ({})", action, name, e));
+        }
+    }
+
+    let target = if running { ServiceState::Running } else { ServiceState::Stopped };
+    let started = Instant::now();
+    loop {
+        let state = service.state()?;
+        if state == target {
+            return Ok(());
+        }
+        if started.elapsed() > STATE_CHANGE_TIMEOUT {
+            return Err(format!("{} is still {} after {}s", name, state, STATE_CHANGE_TIMEOUT.as_secs()));
+        }
+        thread::sleep(Duration::from_millis(250));
+    }
+}
+
+/// Change how a service is started
+pub fn set_start_type(name: &str, start_type: ServiceStartType) -> Result<(), String> {
+    let service = Service::open(name, SERVICE_CHANGE_CONFIG)?;
+
+    let start = match start_type {
+        ServiceStartType::Boot => SERVICE_BOOT_START,
+        ServiceStartType::System => SERVICE_SYSTEM_START,
+        ServiceStartType::Automatic => SERVICE_AUTO_START,
+        ServiceStartType::Manual => SERVICE_DEMAND_START,
+        ServiceStartType::Disabled => SERVICE_DISABLED,
+    };
+
+    unsafe {
+        ChangeServiceConfigW(
+            service.handle,
+            ENUM_SERVICE_TYPE(SERVICE_NO_CHANGE),
+            start,
+            SERVICE_ERROR(SERVICE_NO_CHANGE),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            None,
+            PCWSTR::null(),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            PCWSTR::null(),
+        )
+    }
+    .map_err(|e| format!("Failed to change start type of {} ({})", name, e))
+}
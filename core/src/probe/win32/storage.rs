@@ -0,0 +1,136 @@
+//! Win32 volume free space and drive type
+
+use crate::probe::win32::registry::to_wide;
+use crate::probe::{DiskSpace, DriveKind};
+use windows::core::PCWSTR;
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::Storage::FileSystem::{
+    BusTypeNvme, CreateFileW, GetDiskFreeSpaceExW, GetDriveTypeW, GetVolumeNameForVolumeMountPointW,
+    GetVolumePathNameW, FILE_FLAGS_AND_ATTRIBUTES, FILE_SHARE_READ, FILE_SHARE_WRITE, OPEN_EXISTING,
+};
+use windows::Win32::System::Ioctl::{
+    PropertyStandardQuery, StorageDeviceProperty, StorageDeviceSeekPenaltyProperty, DEVICE_SEEK_PENALTY_DESCRIPTOR,
+    IOCTL_STORAGE_QUERY_PROPERTY, STORAGE_DEVICE_DESCRIPTOR, STORAGE_PROPERTY_ID, STORAGE_PROPERTY_QUERY,
+};
+use windows::Win32::System::IO::DeviceIoControl;
+
+// GetDriveTypeW results (WinBase.h)
+const DRIVE_REMOVABLE: u32 = 2;
+const DRIVE_FIXED: u32 = 3;
+const DRIVE_REMOTE: u32 = 4;
+
+/// Root of the volume holding a path (`D:\` for `D:\Games\Steam`, or the
+/// mounted folder for volumes mounted into a directory)
+fn volume_root(path: &str) -> Result<String, String> {
+    // A bare drive letter ("D" or "D:") would otherwise resolve against the current directory
+    let path = path.trim();
+    let path = match path.len() {
+        1 => format!("{}:\\", path),
+        2 if path.ends_with(':') => format!("{}\\", path),
+        _ => path.to_string(),
+    };
+
+    let mut buffer = vec![0u16; 1024];
+    unsafe { GetVolumePathNameW(PCWSTR::from_raw(to_wide(&path).as_ptr()), &mut buffer) }
+        .map_err(|e| format!("No volume found for '{}' ({})", path, e))?;
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+    Ok(String::from_utf16_lossy(&buffer[..len]))
+}
+
+/// Free space available to the current user and size of a path's volume
+pub fn disk_space(path: &str) -> Result<DiskSpace, String> {
+    let volume = volume_root(path)?;
+    let (mut free_bytes, mut total_bytes) = (0u64, 0u64);
+
+    unsafe {
+        GetDiskFreeSpaceExW(
+            PCWSTR::from_raw(to_wide(&volume).as_ptr()),
+            Some(&mut free_bytes),
+            Some(&mut total_bytes),
+            None,
+        )
+    }
+    .map_err(|e| format!("Failed to read free space of {} ({})", volume, e))?;
+
+    Ok(DiskSpace {
+        volume,
+        free_bytes,
+        total_bytes,
+    })
+}
+
+/// Kind of drive a path's volume is on
+///
+/// Fixed volumes are opened and asked for their bus type (NVMe) and whether
+/// they incur a seek penalty (HDD); volumes spanning several disks can't
+/// answer and report an error.
+pub fn drive_kind(path: &str) -> Result<DriveKind, String> {
+    let root = volume_root(path)?;
+    let root_wide = to_wide(&root);
+
+    match unsafe { GetDriveTypeW(PCWSTR::from_raw(root_wide.as_ptr())) } {
+        DRIVE_REMOVABLE => return Ok(DriveKind::Removable),
+        DRIVE_REMOTE => return Ok(DriveKind::Network),
+        DRIVE_FIXED => {}
+        _ => return Err(format!("{} is not a disk drive", root)),
+    }
+
+    let mut volume_name = vec![0u16; 64];
+    unsafe { GetVolumeNameForVolumeMountPointW(PCWSTR::from_raw(root_wide.as_ptr()), &mut volume_name) }
+        .map_err(|e| format!("Failed to find the volume of {} ({})", root, e))?;
+    let len = volume_name.iter().position(|&c| c == 0).unwrap_or(volume_name.len());
+    // The device is opened without the trailing backslash (which would open the root directory)
+    let device = String::from_utf16_lossy(&volume_name[..len]).trim_end_matches('\\').to_string();
+
+    unsafe {
+        let handle = CreateFileW(
+            PCWSTR::from_raw(to_wide(&device).as_ptr()),
+            0,
+            FILE_SHARE_READ | FILE_SHARE_WRITE,
+            None,
+            OPEN_EXISTING,
+            FILE_FLAGS_AND_ATTRIBUTES(0),
+            HANDLE::default(),
+        )
+        .map_err(|e| format!("Failed to open the volume of {} ({})", root, e))?;
+
+        let mut device_buffer = [0u64; 128];
+        let bus = query_property(handle, StorageDeviceProperty, &mut device_buffer)
+            .map(|()| std::ptr::read_unaligned(device_buffer.as_ptr() as *const STORAGE_DEVICE_DESCRIPTOR).BusType);
+        let mut seek_penalty = DEVICE_SEEK_PENALTY_DESCRIPTOR::default();
+        let seek = query_property(handle, StorageDeviceSeekPenaltyProperty, &mut seek_penalty);
+        let _ = CloseHandle(handle);
+
+        if bus == Ok(BusTypeNvme) {
+            return Ok(DriveKind::Nvme);
+        }
+        seek.map_err(|e| format!("Failed to query the drive of {} ({})", root, e))?;
+        Ok(if seek_penalty.IncursSeekPenalty.as_bool() {
+            DriveKind::Hdd
+        } else {
+            DriveKind::Ssd
+        })
+    }
+}
+
+/// Run a standard IOCTL_STORAGE_QUERY_PROPERTY query into `output`
+unsafe fn query_property<T>(handle: HANDLE, property: STORAGE_PROPERTY_ID, output: &mut T) -> Result<(), String> {
+    let query = STORAGE_PROPERTY_QUERY {
+        PropertyId: property,
+        QueryType: PropertyStandardQuery,
+        ..Default::default()
+    };
+    let mut returned = 0u32;
+
+    DeviceIoControl(
+        handle,
+        IOCTL_STORAGE_QUERY_PROPERTY,
+        Some(&query as *const _ as *const std::ffi::c_void),
+        std::mem::size_of::<STORAGE_PROPERTY_QUERY>() as u32,
+        Some(output as *mut T as *mut std::ffi::c_void),
+        std::mem::size_of::<T>() as u32,
+        Some(&mut returned),
+        None,
+    )
+    .map_err(|e| e.to_string())
+}
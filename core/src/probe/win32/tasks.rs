@@ -0,0 +1,103 @@
+//! Win32 Task Scheduler access (COM)
+
+use crate::probe::{TaskStatus, TASK_NOT_FOUND};
+use windows::core::{BSTR, VARIANT};
+use windows::Win32::Foundation::{
+    ERROR_ACCESS_DENIED, ERROR_FILE_NOT_FOUND, ERROR_PATH_NOT_FOUND, VARIANT_FALSE, VARIANT_TRUE,
+};
+use windows::Win32::System::Com::{
+    CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_INPROC_SERVER, COINIT_MULTITHREADED,
+};
+use windows::Win32::System::TaskScheduler::{
+    IRegisteredTask, ITaskService, TaskScheduler, TASK_STATE_RUNNING,
+};
+
+/// COM initialized for the current thread until dropped
+///
+/// A thread that already joined another apartment (the UI thread) keeps it;
+/// COM is usable there either way, so only our own init is undone.
+struct ComScope(bool);
+
+impl ComScope {
+    fn enter() -> Self {
+        Self(unsafe { CoInitializeEx(None, COINIT_MULTITHREADED) }.is_ok())
+    }
+}
+
+impl Drop for ComScope {
+    fn drop(&mut self) {
+        if self.0 {
+            unsafe { CoUninitialize() };
+        }
+    }
+}
+
+/// Look up a registered task by path (relative paths start at the root folder)
+fn open_task(path: &str) -> Result<IRegisteredTask, String> {
+    unsafe {
+        let service: ITaskService = CoCreateInstance(&TaskScheduler, None, CLSCTX_INPROC_SERVER)
+            .map_err(|e| format!("Failed to create Task Scheduler client ({})", e))?;
+        service
+            .Connect(&VARIANT::default(), &VARIANT::default(), &VARIANT::default(), &VARIANT::default())
+            .map_err(|e| format!("Failed to connect to Task Scheduler ({})", e))?;
+
+        let root = service
+            .GetFolder(&BSTR::from("\\"))
+            .map_err(|e| format!("Failed to open Task Scheduler root folder ({})", e))?;
+
+        root.GetTask(&BSTR::from(path)).map_err(|e| {
+            if e.code() == ERROR_FILE_NOT_FOUND.to_hresult() || e.code() == ERROR_PATH_NOT_FOUND.to_hresult() {
+                TASK_NOT_FOUND.to_string()
+            } else {
+                format!("Failed to open scheduled task {} ({})", path, e)
+            }
+        })
+    }
+}
+
+fn map_change_error(action: &str, path: &str, e: windows::core::Error) -> String {
+    if e.code() == ERROR_ACCESS_DENIED.to_hresult() {
+        "Access denied - admin required".to_string()
+    } else {
+        format!("Failed to {} {} ({})", action, path, e)
+    }
+}
+
+/// Whether a task is enabled and currently running
+pub fn status(path: &str) -> Result<TaskStatus, String> {
+    let _com = ComScope::enter();
+    let task = open_task(path)?;
+
+    unsafe {
+        let enabled = task
+            .Enabled()
+            .map_err(|e| format!("Failed to query scheduled task ({})", e))?;
+        let state = task
+            .State()
+            .map_err(|e| format!("Failed to query scheduled task ({})", e))?;
+
+        Ok(TaskStatus {
+            enabled: enabled.as_bool(),
+            running: state == TASK_STATE_RUNNING,
+        })
+    }
+}
+
+/// Enable or disable a task
+pub fn set_enabled(path: &str, enabled: bool) -> Result<(), String> {
+    let _com = ComScope::enter();
+    let task = open_task(path)?;
+
+    let flag = if enabled { VARIANT_TRUE } else { VARIANT_FALSE };
+    unsafe { task.SetEnabled(flag) }.map_err(|e| {
+        map_change_error(if enabled { "enable" } else { "disable" }, path, e)
+    })
+}
+
+/// Stop every running instance of a task
+pub fn stop(path: &str) -> Result<(), String> {
+    let _com = ComScope::enter();
+    let task = open_task(path)?;
+
+    unsafe { task.Stop(0) }.map_err(|e| map_change_error("stop", path, e))
+}
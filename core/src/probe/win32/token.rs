@@ -0,0 +1,103 @@
+//! Win32 token queries: elevation and the interactive user's SID
+
+use crate::probe::win32::processes;
+use windows::core::PWSTR;
+use windows::Win32::Foundation::{CloseHandle, LocalFree, HANDLE, HLOCAL};
+use windows::Win32::Security::Authorization::ConvertSidToStringSidW;
+use windows::Win32::Security::{
+    GetTokenInformation, TokenElevation, TokenUser, TOKEN_ELEVATION, TOKEN_QUERY, TOKEN_USER,
+};
+use windows::Win32::System::RemoteDesktop::ProcessIdToSessionId;
+use windows::Win32::System::Threading::{
+    GetCurrentProcess, GetCurrentProcessId, OpenProcess, OpenProcessToken,
+    PROCESS_QUERY_LIMITED_INFORMATION,
+};
+
+/// SID of the user logged on to this session, when this process runs
+/// elevated as a different account (None when HKCU is already theirs)
+pub fn interactive_user_sid() -> Option<String> {
+    unsafe {
+        let own_token = open_token(GetCurrentProcess())?;
+        let elevated = is_elevated(own_token);
+        let own_sid = token_user_sid(own_token);
+        let _ = CloseHandle(own_token);
+
+        if !elevated {
+            return None;
+        }
+
+        // The shell runs as the interactive user in our session
+        let mut session = 0;
+        ProcessIdToSessionId(GetCurrentProcessId(), &mut session).ok()?;
+
+        let shell_sid = processes::process_entries()
+            .ok()?
+            .into_iter()
+            .filter(|(_, name)| name.eq_ignore_ascii_case("explorer.exe"))
+            .filter(|&(pid, _)| {
+                let mut process_session = 0;
+                ProcessIdToSessionId(pid, &mut process_session).is_ok() && process_session == session
+            })
+            .find_map(|(pid, _)| process_user_sid(pid))?;
+
+        if Some(&shell_sid) == own_sid.as_ref() {
+            None
+        } else {
+            tracing::debug!("Elevated as another account, using HKU\\{} for HKCU", shell_sid);
+            Some(shell_sid)
+        }
+    }
+}
+
+unsafe fn open_token(process: HANDLE) -> Option<HANDLE> {
+    let mut token = HANDLE::default();
+    OpenProcessToken(process, TOKEN_QUERY, &mut token).ok()?;
+    Some(token)
+}
+
+unsafe fn is_elevated(token: HANDLE) -> bool {
+    let mut elevation = TOKEN_ELEVATION::default();
+    let mut size = 0;
+    GetTokenInformation(
+        token,
+        TokenElevation,
+        Some(&mut elevation as *mut _ as *mut _),
+        std::mem::size_of::<TOKEN_ELEVATION>() as u32,
+        &mut size,
+    )
+    .is_ok()
+        && elevation.TokenIsElevated != 0
+}
+
+/// SID of the account a process runs as
+unsafe fn process_user_sid(pid: u32) -> Option<String> {
+    let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+    let token = open_token(process);
+    let _ = CloseHandle(process);
+
+    let token = token?;
+    let sid = token_user_sid(token);
+    let _ = CloseHandle(token);
+    sid
+}
+
+/// SID of a token's user as an `S-1-...` string
+unsafe fn token_user_sid(token: HANDLE) -> Option<String> {
+    // First call only reports the size needed
+    let mut size = 0;
+    let _ = GetTokenInformation(token, TokenUser, None, 0, &mut size);
+    if size == 0 {
+        return None;
+    }
+
+    // u64 storage keeps TOKEN_USER aligned
+    let mut buffer = vec![0u64; (size as usize).div_ceil(8)];
+    GetTokenInformation(token, TokenUser, Some(buffer.as_mut_ptr() as *mut _), size, &mut size).ok()?;
+    let user = &*(buffer.as_ptr() as *const TOKEN_USER);
+
+    let mut text = PWSTR::null();
+    ConvertSidToStringSidW(user.User.Sid, &mut text).ok()?;
+    let sid = text.to_string().ok();
+    let _ = LocalFree(HLOCAL(text.0 as *mut _));
+    sid
+}
@@ -0,0 +1,109 @@
+//! Reports of check and fix runs for the command-line interface
+
+use crate::checkers::{CheckResult, OverallStatus};
+use crate::fixer::FixResult;
+use serde::Serialize;
+use std::fmt::Write;
+
+/// Output format for command-line reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl OutputFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Some(OutputFormat::Text),
+            "json" => Some(OutputFormat::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Outcome of one pass over a scenario's checks
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub scenario: String,
+    pub scenario_name: String,
+    pub status: OverallStatus,
+    pub passed: usize,
+    /// Checks that ran (skipped checks are not counted)
+    pub total: usize,
+    pub skipped: usize,
+    pub results: Vec<CheckResult>,
+}
+
+impl CheckReport {
+    pub fn new(scenario: &str, scenario_name: &str, results: Vec<CheckResult>) -> Self {
+        Self {
+            scenario: scenario.to_string(),
+            scenario_name: scenario_name.to_string(),
+            status: OverallStatus::from_results(&results),
+            passed: results.iter().filter(|r| r.passed).count(),
+            total: results.iter().filter(|r| !r.skipped).count(),
+            skipped: results.iter().filter(|r| r.skipped).count(),
+            results,
+        }
+    }
+
+    /// Render as human-readable text, one line per check
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Scenario: {} ({})", self.scenario_name, self.scenario);
+
+        for result in &self.results {
+            if result.passed {
+                let _ = writeln!(out, "[PASS] {}: {}", result.name, result.current_value);
+            } else if result.skipped {
+                let _ = writeln!(out, "[SKIP] {}", result.message);
+            } else if result.expected_value.is_empty() {
+                let _ = writeln!(out, "[ERR ] {}", result.message);
+            } else {
+                let _ = writeln!(
+                    out,
+                    "[FAIL] {}: {} (expected {})",
+                    result.name, result.current_value, result.expected_value
+                );
+            }
+        }
+
+        let _ = write!(out, "{}/{} checks passed", self.passed, self.total);
+        if self.skipped > 0 {
+            let _ = write!(out, ", {} skipped", self.skipped);
+        }
+        let _ = writeln!(out);
+        out
+    }
+}
+
+/// Fixes applied to a scenario and the state afterwards
+#[derive(Debug, Clone, Serialize)]
+pub struct FixReport {
+    pub fixes: Vec<FixResult>,
+    pub after: CheckReport,
+}
+
+impl FixReport {
+    /// Render as human-readable text: fix attempts, then the re-check
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+
+        if self.fixes.is_empty() {
+            let _ = writeln!(out, "Nothing to fix");
+        }
+        for fix in &self.fixes {
+            let tag = match (fix.success, fix.skipped) {
+                (true, _) => "FIXED",
+                (false, true) => "SKIP ",
+                (false, false) => "FAIL ",
+            };
+            let _ = writeln!(out, "[{}] {}: {}", tag, fix.check_name, fix.message);
+        }
+
+        let _ = writeln!(out);
+        out.push_str(&self.after.to_text());
+        out
+    }
+}
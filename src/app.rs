@@ -1,7 +1,9 @@
-use crate::checkers::{run_all_checks, CheckResult, OverallStatus};
-use crate::config::Config;
 use crate::notifications;
-use std::collections::HashMap;
+use bench_checklist_core::checkers::{run_all_checks, CheckResult, OverallStatus};
+use bench_checklist_core::config::Config;
+use bench_checklist_core::drift::DriftTracker;
+use bench_checklist_core::fixer::{self, FixResult, PreviousState};
+use bench_checklist_core::probe::SystemProbe;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
@@ -11,6 +13,8 @@ use std::time::Instant;
 #[derive(Clone)]
 pub struct AppState {
     inner: Arc<Mutex<AppStateInner>>,
+    /// Backend used to read and fix system state
+    probe: Arc<dyn SystemProbe>,
     /// Signal for windows to close when app is exiting
     should_exit: Arc<AtomicBool>,
 }
@@ -20,12 +24,14 @@ struct AppStateInner {
     pub config_path: PathBuf,
     pub last_results: Vec<CheckResult>,
     pub last_check_time: Option<Instant>,
-    pub previous_status: HashMap<String, bool>,
+    pub drift: DriftTracker,
     pub notify_on_drift: bool,
+    /// What fixes replaced since the last restore, oldest first
+    pub replaced: Vec<PreviousState>,
 }
 
 impl AppState {
-    pub fn new(config: Config, config_path: PathBuf) -> Self {
+    pub fn new(config: Config, config_path: PathBuf, probe: Arc<dyn SystemProbe>) -> Self {
         let notify_on_drift = config.get_notify_on_drift();
         Self {
             inner: Arc::new(Mutex::new(AppStateInner {
@@ -33,13 +39,20 @@ impl AppState {
                 config_path,
                 last_results: Vec::new(),
                 last_check_time: None,
-                previous_status: HashMap::new(),
+                drift: DriftTracker::new(),
                 notify_on_drift,
+                replaced: Vec::new(),
             })),
+            probe,
             should_exit: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Get the system probe checks and fixes run against
+    pub fn probe(&self) -> &dyn SystemProbe {
+        self.probe.as_ref()
+    }
+
     /// Signal that the app should exit (closes any open windows)
     pub fn signal_exit(&self) {
         self.should_exit.store(true, Ordering::SeqCst);
@@ -52,27 +65,18 @@ impl AppState {
 
     /// Run all checks and update state
     pub fn run_checks(&self) -> (Vec<CheckResult>, OverallStatus) {
-        let mut inner = self.inner.lock().unwrap();
-
-        let checks = inner.config.get_scenario_checks()
-            .map(|c| c.clone())
+        let checks = self.inner.lock().unwrap().config.get_scenario_checks()
+            .cloned()
             .unwrap_or_default();
 
-        let results = run_all_checks(&checks);
+        // Run without the lock so the tray and UI aren't held up by slow probes
+        let results = run_all_checks(self.probe.as_ref(), &checks);
         let status = OverallStatus::from_results(&results);
 
-        // Detect drift (settings that changed from passing to failing)
-        let mut drifted: Vec<&CheckResult> = Vec::new();
-
-        for result in &results {
-            let was_passing = inner.previous_status.get(&result.id).copied().unwrap_or(true);
-
-            if was_passing && !result.passed {
-                drifted.push(result);
-            }
+        let mut inner = self.inner.lock().unwrap();
 
-            inner.previous_status.insert(result.id.clone(), result.passed);
-        }
+        // Detect drift (settings that changed from passing to failing)
+        let drifted = inner.drift.update(&results);
 
         // Notify on drift if enabled
         tracing::debug!("Drift detection: notify_on_drift={}, drifted_count={}", inner.notify_on_drift, drifted.len());
@@ -92,6 +96,25 @@ impl AppState {
         self.inner.lock().unwrap().last_results.clone()
     }
 
+    /// Keep what a run of fixes replaced so it can be restored later
+    pub fn record_fixes(&self, fixes: &[FixResult]) {
+        let mut inner = self.inner.lock().unwrap();
+        inner
+            .replaced
+            .extend(fixes.iter().filter_map(|f| f.previous.clone()));
+    }
+
+    /// Check if there is anything fixes replaced that can be restored
+    pub fn can_restore(&self) -> bool {
+        !self.inner.lock().unwrap().replaced.is_empty()
+    }
+
+    /// Put back everything fixes replaced since the last restore
+    pub fn restore_replaced(&self) -> Vec<Result<String, String>> {
+        let replaced = std::mem::take(&mut self.inner.lock().unwrap().replaced);
+        fixer::restore_all(self.probe.as_ref(), &replaced)
+    }
+
     /// Get the current overall status
     pub fn get_status(&self) -> OverallStatus {
         let inner = self.inner.lock().unwrap();
@@ -210,7 +233,7 @@ impl AppState {
         inner.config.active_scenario = scenario_id.to_string();
 
         // Reset drift detection (clear previous status)
-        inner.previous_status.clear();
+        inner.drift.reset();
 
         // Update notify_on_drift from new scenario
         inner.notify_on_drift = inner
@@ -227,7 +250,7 @@ impl AppState {
     }
 
     /// Add a new check to the current scenario
-    pub fn add_check(&self, check: crate::config::CheckConfig) {
+    pub fn add_check(&self, check: bench_checklist_core::config::CheckConfig) {
         let mut inner = self.inner.lock().unwrap();
         let active_id = inner.config.active_scenario.clone();
         if let Some(scenario) = inner.config.root.scenarios.get_mut(&active_id) {
@@ -245,7 +268,7 @@ impl AppState {
     }
 
     /// Update an existing check in the current scenario
-    pub fn update_check(&self, check: crate::config::CheckConfig) {
+    pub fn update_check(&self, check: bench_checklist_core::config::CheckConfig) {
         let mut inner = self.inner.lock().unwrap();
         let active_id = inner.config.active_scenario.clone();
         if let Some(scenario) = inner.config.root.scenarios.get_mut(&active_id) {
@@ -256,7 +279,7 @@ impl AppState {
     }
 
     /// Add a new scenario to the config
-    pub fn add_scenario(&self, id: &str, scenario: crate::config::Scenario) -> anyhow::Result<()> {
+    pub fn add_scenario(&self, id: &str, scenario: bench_checklist_core::config::Scenario) -> anyhow::Result<()> {
         let mut inner = self.inner.lock().unwrap();
 
         if inner.config.root.scenarios.contains_key(id) {
@@ -287,7 +310,7 @@ impl AppState {
         }
 
         let passed = results.iter().filter(|r| r.passed).count();
-        let total = results.len();
+        let total = results.iter().filter(|r| !r.skipped).count();
         let status = OverallStatus::from_results(results);
 
         let status_text = match status {
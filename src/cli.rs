@@ -0,0 +1,527 @@
+//! Headless command-line mode
+//!
+//! When the executable is started with arguments it runs a single command,
+//! prints the result to stdout and exits without creating the tray icon or
+//! any window. Exit codes:
+//!
+//! - `0`: success (all checks passed, config valid)
+//! - `1`: some checks failed, or the config is invalid
+//! - `2`: usage error, unknown scenario, or the config could not be loaded
+
+use bench_checklist_core::checkers::{run_all_checks, OverallStatus};
+use bench_checklist_core::config::Config;
+use bench_checklist_core::fixer::{self, PreviousState};
+use bench_checklist_core::probe::{RecordingProbe, ReplayProbe, Snapshot, SystemProbe};
+use bench_checklist_core::report::{CheckReport, FixReport, OutputFormat};
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const EXIT_OK: i32 = 0;
+pub const EXIT_FAILED: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+
+const USAGE: &str = "\
+Usage: bench_checklist [COMMAND] [OPTIONS]
+
+Without a command the tray application starts.
+
+Commands:
+  check             Run the scenario's checks once
+  fix               Fix failing checks, then run the checks again
+  snapshot          Run the checks once and save everything they read
+  restore           Put back what a fix replaced, from a file saved by fix --output
+  list-scenarios    List scenarios in the config
+  validate-config   Report problems in the config file
+  help              Show this message
+
+Options:
+  --scenario <ID>   Scenario to use (default: the config's default scenario)
+  --format <FMT>    Output format: text (default) or json
+  --config <PATH>   Config file (default: config\\checklist.json)
+  --output <PATH>   Snapshot file to write (snapshot), or where to save what fixes replaced (fix)
+  --input <PATH>    File of replaced state saved by fix --output (restore)
+  --replay <PATH>   Evaluate against a saved snapshot instead of this machine (check)
+
+Exit codes: 0 = all passed, 1 = checks failed or config invalid, 2 = usage error";
+
+/// A parsed command line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Invocation {
+    pub command: Command,
+    pub scenario: Option<String>,
+    pub format: OutputFormat,
+    pub config_path: Option<PathBuf>,
+    pub output_path: Option<PathBuf>,
+    pub input_path: Option<PathBuf>,
+    pub replay_path: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Check,
+    Fix,
+    Snapshot,
+    Restore,
+    ListScenarios,
+    ValidateConfig,
+    Help,
+}
+
+impl Command {
+    fn as_str(self) -> &'static str {
+        match self {
+            Command::Check => "check",
+            Command::Fix => "fix",
+            Command::Snapshot => "snapshot",
+            Command::Restore => "restore",
+            Command::ListScenarios => "list-scenarios",
+            Command::ValidateConfig => "validate-config",
+            Command::Help => "help",
+        }
+    }
+}
+
+/// Commands an option applies to, or None for an unknown option
+fn option_commands(option: &str) -> Option<&'static [Command]> {
+    use Command::*;
+
+    Some(match option {
+        "--scenario" => &[Check, Fix, Snapshot],
+        "--format" => &[Check, Fix, Snapshot, Restore, ListScenarios, ValidateConfig],
+        "--config" => &[Check, Fix, Snapshot, ListScenarios, ValidateConfig],
+        "--output" => &[Fix, Snapshot],
+        "--input" => &[Restore],
+        "--replay" => &[Check],
+        _ => return None,
+    })
+}
+
+/// Parse command-line arguments (without the program name)
+pub fn parse_args(args: &[String]) -> Result<Invocation, String> {
+    let mut iter = args.iter();
+
+    let command = match iter.next().map(|s| s.as_str()) {
+        Some("check") => Command::Check,
+        Some("fix") => Command::Fix,
+        Some("snapshot") => Command::Snapshot,
+        Some("restore") => Command::Restore,
+        Some("list-scenarios") => Command::ListScenarios,
+        Some("validate-config") => Command::ValidateConfig,
+        Some("help") | Some("--help") | Some("-h") | None => Command::Help,
+        Some(other) => return Err(format!("Unknown command: {}", other)),
+    };
+
+    let mut invocation = Invocation {
+        command,
+        scenario: None,
+        format: OutputFormat::Text,
+        config_path: None,
+        output_path: None,
+        input_path: None,
+        replay_path: None,
+    };
+
+    while let Some(arg) = iter.next() {
+        if let Some(commands) = option_commands(arg) {
+            if !commands.contains(&command) {
+                let names: Vec<&str> = commands.iter().map(|c| c.as_str()).collect();
+                return Err(format!("{} can only be used with {}", arg, names.join(", ")));
+            }
+        }
+
+        let mut value = |name: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+
+        match arg.as_str() {
+            "--scenario" => invocation.scenario = Some(value("--scenario")?),
+            "--format" => {
+                let format = value("--format")?;
+                invocation.format = OutputFormat::parse(&format)
+                    .ok_or_else(|| format!("Unknown format: {}", format))?;
+            }
+            "--config" => invocation.config_path = Some(PathBuf::from(value("--config")?)),
+            "--output" => invocation.output_path = Some(PathBuf::from(value("--output")?)),
+            "--input" => invocation.input_path = Some(PathBuf::from(value("--input")?)),
+            "--replay" => invocation.replay_path = Some(PathBuf::from(value("--replay")?)),
+            other => return Err(format!("Unknown option: {}", other)),
+        }
+    }
+
+    if invocation.command == Command::Snapshot && invocation.output_path.is_none() {
+        return Err("snapshot requires --output <PATH>".to_string());
+    }
+    if invocation.command == Command::Restore && invocation.input_path.is_none() {
+        return Err("restore requires --input <PATH>".to_string());
+    }
+
+    Ok(invocation)
+}
+
+/// Run a command line and return the process exit code
+///
+/// `probe` reads this machine; without one (on hosts other than Windows) only
+/// commands that don't need it work.
+pub fn run(args: &[String], default_config_path: PathBuf, probe: Option<&dyn SystemProbe>) -> i32 {
+    let invocation = match parse_args(args) {
+        Ok(invocation) => invocation,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return EXIT_USAGE;
+        }
+    };
+
+    if invocation.command == Command::Help {
+        println!("{}", USAGE);
+        return EXIT_OK;
+    }
+    // Restoring doesn't depend on the config
+    if invocation.command == Command::Restore {
+        return probe.map_or_else(no_live_probe, |probe| run_restore(&invocation, probe));
+    }
+
+    let config_path = invocation.config_path.clone().unwrap_or(default_config_path);
+    let config = match Config::load(&config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            // A config that doesn't load is the first problem validate-config reports
+            if invocation.command == Command::ValidateConfig {
+                return print_validation(&invocation, vec![format!("{:#}", e)], 0);
+            }
+            eprintln!("Error: {:#}", e);
+            return EXIT_USAGE;
+        }
+    };
+
+    match invocation.command {
+        Command::Check => match (&invocation.replay_path, probe) {
+            (Some(path), _) => run_replay(&invocation, config, path),
+            (None, Some(probe)) => run_check(&invocation, config, probe),
+            (None, None) => no_live_probe(),
+        },
+        Command::Fix => probe.map_or_else(no_live_probe, |probe| run_fix(&invocation, config, probe)),
+        Command::Snapshot => probe.map_or_else(no_live_probe, |probe| run_snapshot(&invocation, config, probe)),
+        Command::ListScenarios => list_scenarios(&invocation, &config),
+        Command::ValidateConfig => validate_config(&invocation, &config),
+        Command::Help | Command::Restore => EXIT_OK,
+    }
+}
+
+/// Report a command that reads this machine on a host without a live probe
+fn no_live_probe() -> i32 {
+    eprintln!("Error: checking this machine needs Windows; use check --replay <PATH> to evaluate a snapshot");
+    EXIT_USAGE
+}
+
+/// Switch to the scenario requested on the command line, if any
+fn select_scenario(invocation: &Invocation, config: &mut Config) -> Result<(), String> {
+    if let Some(id) = &invocation.scenario {
+        if !config.root.scenarios.contains_key(id) {
+            return Err(format!("Scenario '{}' not found", id));
+        }
+        config.active_scenario = id.clone();
+    }
+    Ok(())
+}
+
+/// Run the active scenario's checks once
+fn check_report(config: &Config, probe: &dyn SystemProbe) -> CheckReport {
+    let checks = config.get_scenario_checks().cloned().unwrap_or_default();
+    let scenario_name = config
+        .get_active_scenario_data()
+        .map(|s| s.name.clone())
+        .unwrap_or_default();
+
+    CheckReport::new(
+        &config.active_scenario,
+        &scenario_name,
+        run_all_checks(probe, &checks),
+    )
+}
+
+fn status_exit_code(status: OverallStatus) -> i32 {
+    if status == OverallStatus::AllPassed {
+        EXIT_OK
+    } else {
+        EXIT_FAILED
+    }
+}
+
+fn print_output<T: Serialize>(format: OutputFormat, value: &T, text: impl FnOnce() -> String) {
+    match format {
+        OutputFormat::Text => print!("{}", text()),
+        OutputFormat::Json => match serde_json::to_string_pretty(value) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error: failed to serialize output: {}", e),
+        },
+    }
+}
+
+fn run_check(invocation: &Invocation, mut config: Config, probe: &dyn SystemProbe) -> i32 {
+    if let Err(e) = select_scenario(invocation, &mut config) {
+        eprintln!("Error: {}", e);
+        return EXIT_USAGE;
+    }
+
+    let report = check_report(&config, probe);
+    print_output(invocation.format, &report, || report.to_text());
+    status_exit_code(report.status)
+}
+
+fn run_replay(invocation: &Invocation, config: Config, path: &Path) -> i32 {
+    let snapshot = match Snapshot::load(path) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            eprintln!("Error: {:#}", e);
+            return EXIT_USAGE;
+        }
+    };
+
+    // Default to the scenario the snapshot was captured for
+    let mut invocation = invocation.clone();
+    if invocation.scenario.is_none() {
+        invocation.scenario = snapshot.scenario.clone();
+    }
+
+    run_check(&invocation, config, &ReplayProbe::new(snapshot))
+}
+
+fn run_snapshot(invocation: &Invocation, mut config: Config, probe: &dyn SystemProbe) -> i32 {
+    if let Err(e) = select_scenario(invocation, &mut config) {
+        eprintln!("Error: {}", e);
+        return EXIT_USAGE;
+    }
+
+    let recorder = RecordingProbe::new(probe);
+    let report = check_report(&config, &recorder);
+
+    let mut snapshot = recorder.into_snapshot();
+    snapshot.scenario = Some(config.active_scenario.clone());
+
+    // parse_args guarantees an output path for snapshot
+    let output_path = invocation.output_path.clone().unwrap_or_default();
+    if let Err(e) = snapshot.save(&output_path) {
+        eprintln!("Error: {:#}", e);
+        return EXIT_USAGE;
+    }
+    eprintln!("Snapshot saved to {}", output_path.display());
+
+    print_output(invocation.format, &report, || report.to_text());
+    status_exit_code(report.status)
+}
+
+fn run_fix(invocation: &Invocation, mut config: Config, probe: &dyn SystemProbe) -> i32 {
+    if let Err(e) = select_scenario(invocation, &mut config) {
+        eprintln!("Error: {}", e);
+        return EXIT_USAGE;
+    }
+
+    let before = check_report(&config, probe);
+    let failing_ids: Vec<String> = before
+        .results
+        .iter()
+        .filter(|r| r.failed())
+        .map(|r| r.id.clone())
+        .collect();
+
+    let checks = config.get_scenario_checks().cloned().unwrap_or_default();
+    let fixes = fixer::fix_all(probe, &checks, &failing_ids);
+
+    let report = FixReport {
+        fixes,
+        after: check_report(&config, probe),
+    };
+    print_output(invocation.format, &report, || report.to_text());
+
+    // The fixes have already been applied, so a failed save doesn't hide the report
+    if let Some(path) = &invocation.output_path {
+        let replaced: Vec<&PreviousState> = report.fixes.iter().filter_map(|f| f.previous.as_ref()).collect();
+        if replaced.is_empty() {
+            // Keep a file saved by an earlier fix restorable
+            eprintln!("Nothing replaced, {} left unchanged", path.display());
+        } else if let Err(e) = save_replaced(path, &replaced) {
+            eprintln!("Error: {}", e);
+            return EXIT_USAGE;
+        } else {
+            eprintln!("Replaced state saved to {}", path.display());
+        }
+    }
+
+    status_exit_code(report.after.status)
+}
+
+fn save_replaced(path: &Path, replaced: &[&PreviousState]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(replaced)
+        .map_err(|e| format!("Failed to serialize replaced state: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn load_replaced(path: &Path) -> Result<Vec<PreviousState>, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse {}: {}", path.display(), e))
+}
+
+#[derive(Serialize)]
+struct RestoreResult {
+    success: bool,
+    message: String,
+}
+
+/// Put back every state in a file saved by `fix --output`
+fn run_restore(invocation: &Invocation, probe: &dyn SystemProbe) -> i32 {
+    // parse_args guarantees an input path for restore
+    let input_path = invocation.input_path.clone().unwrap_or_default();
+    let replaced = match load_replaced(&input_path) {
+        Ok(replaced) => replaced,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            return EXIT_USAGE;
+        }
+    };
+
+    let results: Vec<RestoreResult> = fixer::restore_all(probe, &replaced)
+        .into_iter()
+        .map(|result| match result {
+            Ok(message) => RestoreResult { success: true, message },
+            Err(message) => RestoreResult { success: false, message },
+        })
+        .collect();
+
+    print_output(invocation.format, &results, || {
+        if results.is_empty() {
+            return "Nothing to restore\n".to_string();
+        }
+        results
+            .iter()
+            .map(|r| format!("[{}] {}\n", if r.success { "RESTORED" } else { "FAIL" }, r.message))
+            .collect()
+    });
+
+    if results.iter().all(|r| r.success) {
+        EXIT_OK
+    } else {
+        EXIT_FAILED
+    }
+}
+
+#[derive(Serialize)]
+struct ScenarioSummary {
+    id: String,
+    name: String,
+    description: String,
+    checks: usize,
+    default: bool,
+}
+
+fn list_scenarios(invocation: &Invocation, config: &Config) -> i32 {
+    let mut scenarios: Vec<ScenarioSummary> = config
+        .root
+        .scenarios
+        .iter()
+        .map(|(id, scenario)| ScenarioSummary {
+            id: id.clone(),
+            name: scenario.name.clone(),
+            description: scenario.description.clone(),
+            checks: scenario.checks.len(),
+            default: *id == config.active_scenario,
+        })
+        .collect();
+
+    // Sort by ID for consistent ordering
+    scenarios.sort_by(|a, b| a.id.cmp(&b.id));
+
+    print_output(invocation.format, &scenarios, || {
+        scenarios
+            .iter()
+            .map(|s| {
+                format!(
+                    "{} {} - {} ({} checks)\n",
+                    if s.default { "*" } else { " " },
+                    s.id,
+                    s.name,
+                    s.checks
+                )
+            })
+            .collect()
+    });
+    EXIT_OK
+}
+
+#[derive(Serialize)]
+struct ValidationReport {
+    valid: bool,
+    problems: Vec<String>,
+}
+
+fn validate_config(invocation: &Invocation, config: &Config) -> i32 {
+    print_validation(invocation, config.validate(), config.root.scenarios.len())
+}
+
+fn print_validation(invocation: &Invocation, problems: Vec<String>, scenario_count: usize) -> i32 {
+    let report = ValidationReport {
+        valid: problems.is_empty(),
+        problems,
+    };
+
+    print_output(invocation.format, &report, || {
+        if report.valid {
+            format!("Config OK: {} scenarios\n", scenario_count)
+        } else {
+            report.problems.iter().map(|p| format!("{}\n", p)).collect()
+        }
+    });
+
+    if report.valid {
+        EXIT_OK
+    } else {
+        EXIT_FAILED
+    }
+}
+
+/// Attach to the parent console so output is visible from a terminal
+/// (the executable is built for the windows subsystem and has no console of its own)
+#[cfg(windows)]
+pub fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let invocation = parse_args(&args("check --scenario gaming_fps --format json")).unwrap();
+        assert_eq!(invocation.command, Command::Check);
+        assert_eq!(invocation.scenario.as_deref(), Some("gaming_fps"));
+        assert_eq!(invocation.format, OutputFormat::Json);
+
+        assert_eq!(parse_args(&[]).unwrap().command, Command::Help);
+        assert!(parse_args(&args("check --format xml")).is_err());
+        assert!(parse_args(&args("check --scenario")).is_err());
+        assert!(parse_args(&args("run")).is_err());
+
+        let restore = parse_args(&args("restore --input replaced.json")).unwrap();
+        assert_eq!(restore.command, Command::Restore);
+        assert!(parse_args(&args("restore")).is_err());
+        assert!(parse_args(&args("check --input replaced.json")).is_err());
+
+        // Options the command doesn't use are rejected rather than ignored
+        assert!(parse_args(&args("fix --scenario gaming_fps --output replaced.json")).is_ok());
+        assert!(parse_args(&args("list-scenarios --scenario nope")).is_err());
+        assert!(parse_args(&args("validate-config --scenario gaming_fps")).is_err());
+        assert!(parse_args(&args("check --output out.json")).is_err());
+        assert!(parse_args(&args("restore --input replaced.json --config checklist.json")).is_err());
+    }
+}
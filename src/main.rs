@@ -1,25 +1,53 @@
 #![windows_subsystem = "windows"]
 
+// The tray application, its UI and the live probe are Windows-only; other
+// hosts get the command line, which can still evaluate saved snapshots
+#[cfg(windows)]
 mod app;
+#[cfg(windows)]
 mod autostart;
-mod check_library;
-mod checkers;
-mod config;
-mod fixer;
+mod cli;
+#[cfg(windows)]
 mod notifications;
+#[cfg(windows)]
 mod ui;
 
-use app::AppState;
-use config::Config;
+use bench_checklist_core::probe::SystemProbe;
 use std::path::PathBuf;
+
+#[cfg(windows)]
+use app::AppState;
+#[cfg(windows)]
+use bench_checklist_core::config::Config;
+#[cfg(windows)]
+use bench_checklist_core::probe::Win32Probe;
+#[cfg(windows)]
 use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(windows)]
 use std::sync::Arc;
+#[cfg(windows)]
 use std::thread;
+#[cfg(windows)]
 use std::time::Duration;
+#[cfg(windows)]
 use tray_icon::TrayIconEvent;
+#[cfg(windows)]
 use ui::tray::{self, MENU_AUTOSTART, MENU_CHECK_NOW, MENU_EXIT, MENU_SETTINGS};
 
+#[cfg(not(windows))]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    run_cli(&args);
+}
+
+#[cfg(windows)]
 fn main() -> anyhow::Result<()> {
+    // Any arguments select headless command-line mode
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if !args.is_empty() {
+        run_cli(&args);
+    }
+
     // Initialize logging
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -38,7 +66,7 @@ fn main() -> anyhow::Result<()> {
     tracing::info!("Loaded config with {} checks", check_count);
 
     // Create application state
-    let app_state = AppState::new(config, config_path);
+    let app_state = AppState::new(config, config_path, Arc::new(Win32Probe::new()));
 
     // Create the system tray icon
     let tray = tray::create_tray_icon()?;
@@ -168,7 +196,34 @@ fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Run a single command without the tray or UI, then exit
+fn run_cli(args: &[String]) -> ! {
+    #[cfg(windows)]
+    cli::attach_console();
+
+    // Keep stdout clean for machine-readable output
+    tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::from_default_env()
+                .add_directive(tracing::Level::WARN.into()),
+        )
+        .init();
+
+    // Checks run once here, so CPU load waits for a full sample window
+    #[cfg(windows)]
+    let win32 = Win32Probe::new().waiting_for_cpu_samples();
+    #[cfg(windows)]
+    let probe: Option<&dyn SystemProbe> = Some(&win32);
+    #[cfg(not(windows))]
+    let probe: Option<&dyn SystemProbe> = None;
+
+    let code = cli::run(args, get_config_path(), probe);
+    std::process::exit(code);
+}
+
 /// Polling loop that runs checks periodically
+#[cfg(windows)]
 fn polling_loop(app_state: AppState, running: Arc<AtomicBool>) {
     while running.load(Ordering::SeqCst) {
         let interval = app_state.get_poll_interval();
@@ -220,6 +275,7 @@ fn get_config_path() -> PathBuf {
 }
 
 /// Load configuration, creating default if needed
+#[cfg(windows)]
 fn load_config(path: &PathBuf) -> anyhow::Result<Config> {
     if path.exists() {
         Config::load(path)
@@ -232,6 +288,7 @@ fn load_config(path: &PathBuf) -> anyhow::Result<Config> {
 }
 
 /// Open settings window if not already open
+#[cfg(windows)]
 fn open_settings(settings_open: &Arc<AtomicBool>, app_state: &AppState) {
     if !settings_open.load(Ordering::SeqCst) {
         settings_open.store(true, Ordering::SeqCst);
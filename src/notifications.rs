@@ -1,4 +1,4 @@
-use crate::checkers::{CheckResult, OverallStatus};
+use bench_checklist_core::checkers::{CheckResult, OverallStatus};
 use winrt_notification::{Duration, Sound, Toast};
 
 /// Send a toast notification for drift detection
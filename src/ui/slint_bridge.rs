@@ -2,9 +2,10 @@
 //! Connects Slint UI components to the Rust AppState
 
 use crate::app::AppState;
-use crate::check_library::{get_library, CATEGORIES};
-use crate::config::{CheckConfig, CheckType};
-use crate::fixer;
+use bench_checklist_core::check_library::{get_library, get_library_for, CATEGORIES};
+use bench_checklist_core::checkers::power_plan;
+use bench_checklist_core::config::{CheckConfig, CheckKind, CheckType};
+use bench_checklist_core::fixer;
 use slint::{ModelRc, SharedString, VecModel};
 
 // Include the generated Slint code
@@ -32,6 +33,7 @@ fn refresh_all(ui: &MainWindow, app_state: &AppState) {
     refresh_scenarios(ui, app_state);
     refresh_library(ui, app_state);
     refresh_settings(ui, app_state);
+    ui.set_can_restore(app_state.can_restore());
 }
 
 /// Refresh check list from AppState
@@ -55,6 +57,7 @@ fn refresh_checks(ui: &MainWindow, app_state: &AppState) {
                 id: r.id.clone().into(),
                 name: r.name.clone().into(),
                 passed: r.passed,
+                skipped: r.skipped,
                 enabled,
                 current_value: r.current_value.clone().into(),
                 expected_value: r.expected_value.clone().into(),
@@ -65,7 +68,7 @@ fn refresh_checks(ui: &MainWindow, app_state: &AppState) {
 
     // Calculate pass/fail counts
     let passed = items.iter().filter(|c| c.passed).count() as i32;
-    let total = items.len() as i32;
+    let total = items.iter().filter(|c| !c.skipped).count() as i32;
 
     ui.set_checks(ModelRc::new(VecModel::from(items)));
     ui.set_passed_count(passed);
@@ -97,7 +100,7 @@ fn refresh_scenarios(ui: &MainWindow, app_state: &AppState) {
 
 /// Refresh check library data
 fn refresh_library(ui: &MainWindow, app_state: &AppState) {
-    let library = get_library();
+    let library = get_library_for(app_state.probe());
     let config = app_state.get_config();
     let existing_ids: Vec<String> = config
         .get_scenario_checks()
@@ -122,6 +125,15 @@ fn refresh_library(ui: &MainWindow, app_state: &AppState) {
     ui.set_library_categories(ModelRc::new(VecModel::from(categories)));
 }
 
+/// Refresh the power plans offered in the check editor
+fn refresh_power_schemes(ui: &MainWindow, app_state: &AppState) {
+    let schemes: Vec<SharedString> = power_plan::scheme_choices(app_state.probe())
+        .into_iter()
+        .map(Into::into)
+        .collect();
+    ui.set_power_schemes(ModelRc::new(VecModel::from(schemes)));
+}
+
 /// Refresh settings from AppState
 fn refresh_settings(ui: &MainWindow, app_state: &AppState) {
     ui.set_poll_interval(app_state.get_poll_interval() as i32);
@@ -220,18 +232,35 @@ fn setup_callbacks(ui: &MainWindow, app_state: &AppState) {
 
     // Add check clicked (opens editor)
     ui.on_add_check_clicked({
+        let app_state = app_state.clone();
         let ui_weak = ui.as_weak();
         move || {
             if let Some(ui) = ui_weak.upgrade() {
+                refresh_power_schemes(&ui, &app_state);
                 ui.set_editor_data(CheckEditorData {
                     id: "".into(),
                     name: "".into(),
                     check_type: "PowerScheme".into(),
                     enabled: true,
+                    laptop_only: false,
                     expected_value: "high_performance".into(),
                     registry_path: "".into(),
                     registry_key: "".into(),
+                    registry_view: "".into(),
                     process_name: "".into(),
+                    process_path: "".into(),
+                    min_instances: "".into(),
+                    max_instances: "".into(),
+                    service_name: "".into(),
+                    service_state: "".into(),
+                    start_type: "".into(),
+                    task_name: "".into(),
+                    power_setting: "".into(),
+                    power_source: "".into(),
+                    display: "".into(),
+                    sample_seconds: "".into(),
+                    path: "".into(),
+                    unit: "".into(),
                     is_editing: false,
                 });
                 ui.set_show_check_editor(true);
@@ -248,6 +277,7 @@ fn setup_callbacks(ui: &MainWindow, app_state: &AppState) {
             if let Ok(checks) = config.get_scenario_checks() {
                 if let Some(check) = checks.iter().find(|c| c.id == id.as_str()) {
                     if let Some(ui) = ui_weak.upgrade() {
+                        refresh_power_schemes(&ui, &app_state);
                         ui.set_editor_data(check_to_editor_data(check));
                         ui.set_show_check_editor(true);
                     }
@@ -274,7 +304,15 @@ fn setup_callbacks(ui: &MainWindow, app_state: &AppState) {
         let app_state = app_state.clone();
         let ui_weak = ui.as_weak();
         move |data| {
-            let check = editor_data_to_check(&data);
+            let check = match editor_data_to_check(&data) {
+                Ok(check) => check,
+                Err(e) => {
+                    if let Some(ui) = ui_weak.upgrade() {
+                        ui.set_status_message(format!("Check not saved: {}", e).into());
+                    }
+                    return;
+                }
+            };
             if data.is_editing {
                 app_state.update_check(check);
             } else {
@@ -311,7 +349,7 @@ fn setup_callbacks(ui: &MainWindow, app_state: &AppState) {
         move |id| {
             let library = get_library();
             if let Some(lc) = library.iter().find(|l| l.id == id.as_str()) {
-                let check = library_check_to_config(lc);
+                let check = lc.to_check_config();
                 app_state.add_check(check);
                 app_state.run_checks();
                 if let Some(ui) = ui_weak.upgrade() {
@@ -346,92 +384,128 @@ fn setup_callbacks(ui: &MainWindow, app_state: &AppState) {
         move || {
             let config = app_state.get_config();
             if let Ok(checks) = config.get_scenario_checks() {
-                let results = app_state.get_last_results();
-                let failed_checks: Vec<&CheckConfig> = checks
+                let failing_ids: Vec<String> = app_state
+                    .get_last_results()
                     .iter()
-                    .filter(|c| {
-                        results.iter().any(|r| r.id == c.id && !r.passed)
-                    })
+                    .filter(|r| r.failed())
+                    .map(|r| r.id.clone())
                     .collect();
 
-                let mut fixed_count = 0;
-                for check in failed_checks {
-                    let fix_result = fixer::fix_check(check);
-                    if fix_result.success {
-                        fixed_count += 1;
-                    }
-                }
+                let fixes = fixer::fix_all(app_state.probe(), checks, &failing_ids);
+                let fixed_count = fixes.iter().filter(|f| f.success).count();
+                app_state.record_fixes(&fixes);
 
                 // Re-run checks after fixing
                 app_state.run_checks();
 
                 if let Some(ui) = ui_weak.upgrade() {
                     refresh_checks(&ui, &app_state);
+                    ui.set_can_restore(app_state.can_restore());
                     ui.set_status_message(format!("Fixed {} checks", fixed_count).into());
                 }
             }
         }
     });
+
+    // Restore clicked (puts back what fixes replaced)
+    ui.on_restore_clicked({
+        let app_state = app_state.clone();
+        let ui_weak = ui.as_weak();
+        move || {
+            let results = app_state.restore_replaced();
+            let restored_count = results.iter().filter(|r| r.is_ok()).count();
+            let first_error = results.into_iter().find_map(Result::err);
+
+            // Re-run checks after restoring
+            app_state.run_checks();
+
+            if let Some(ui) = ui_weak.upgrade() {
+                refresh_checks(&ui, &app_state);
+                ui.set_can_restore(app_state.can_restore());
+                ui.set_status_message(match first_error {
+                    Some(e) => format!("Restored {} settings, error: {}", restored_count, e).into(),
+                    None => format!("Restored {} settings", restored_count).into(),
+                });
+            }
+        }
+    });
 }
 
 /// Convert CheckConfig to CheckEditorData
 fn check_to_editor_data(check: &CheckConfig) -> CheckEditorData {
+    let field = |name: &str| -> SharedString { check.kind.field(name).unwrap_or_default().into() };
+
     CheckEditorData {
         id: check.id.clone().into(),
         name: check.name.clone().into(),
-        check_type: format!("{:?}", check.check_type).into(),
+        check_type: format!("{:?}", check.check_type()).into(),
         enabled: check.enabled,
-        expected_value: check.expected_value.clone().unwrap_or_default().into(),
-        registry_path: check.registry_path.clone().unwrap_or_default().into(),
-        registry_key: check.registry_key.clone().unwrap_or_default().into(),
-        process_name: check.process_name.clone().unwrap_or_default().into(),
+        laptop_only: check.laptop_only,
+        expected_value: field("expected_value"),
+        registry_path: field("registry_path"),
+        registry_key: field("registry_key"),
+        registry_view: field("registry_view"),
+        process_name: field("process_name"),
+        process_path: field("process_path"),
+        min_instances: field("min_instances"),
+        max_instances: field("max_instances"),
+        service_name: field("service_name"),
+        service_state: field("service_state"),
+        start_type: field("start_type"),
+        task_name: field("task_name"),
+        power_setting: field("power_setting"),
+        power_source: field("power_source"),
+        display: field("display"),
+        sample_seconds: field("sample_seconds"),
+        path: field("path"),
+        unit: field("unit"),
         is_editing: true,
     }
 }
 
 /// Convert CheckEditorData to CheckConfig
-fn editor_data_to_check(data: &CheckEditorData) -> CheckConfig {
+/// Returns Err describing the first missing or invalid field, or the first
+/// problem validation finds in the check
+fn editor_data_to_check(data: &CheckEditorData) -> Result<CheckConfig, String> {
     let check_type = parse_check_type(&data.check_type.to_string());
 
-    CheckConfig {
+    let kind = CheckKind::from_fields(check_type, |field| {
+        let value = match field {
+            "expected_value" => &data.expected_value,
+            "registry_path" => &data.registry_path,
+            "registry_key" => &data.registry_key,
+            "registry_view" => &data.registry_view,
+            "process_name" => &data.process_name,
+            "process_path" => &data.process_path,
+            "min_instances" => &data.min_instances,
+            "max_instances" => &data.max_instances,
+            "service_name" => &data.service_name,
+            "service_state" => &data.service_state,
+            "start_type" => &data.start_type,
+            "task_name" => &data.task_name,
+            "power_setting" => &data.power_setting,
+            "power_source" => &data.power_source,
+            "display" => &data.display,
+            "sample_seconds" => &data.sample_seconds,
+            "path" => &data.path,
+            "unit" => &data.unit,
+            _ => return None,
+        };
+        Some(value.to_string())
+    })?;
+
+    let check = CheckConfig {
         id: data.id.to_string(),
         name: data.name.to_string(),
-        check_type,
         enabled: data.enabled,
-        expected_value: if data.expected_value.is_empty() {
-            None
-        } else {
-            Some(data.expected_value.to_string())
-        },
-        registry_path: if data.registry_path.is_empty() {
-            None
-        } else {
-            Some(data.registry_path.to_string())
-        },
-        registry_key: if data.registry_key.is_empty() {
-            None
-        } else {
-            Some(data.registry_key.to_string())
-        },
-        process_name: if data.process_name.is_empty() {
-            None
-        } else {
-            Some(data.process_name.to_string())
-        },
-    }
-}
-
-/// Convert LibraryCheck to CheckConfig
-fn library_check_to_config(lc: &crate::check_library::LibraryCheck) -> CheckConfig {
-    CheckConfig {
-        id: lc.id.to_string(),
-        name: lc.name.to_string(),
-        check_type: lc.check_type.clone(),
-        enabled: true,
-        expected_value: lc.expected_value.map(|s| s.to_string()),
-        registry_path: lc.registry_path.map(|s| s.to_string()),
-        registry_key: lc.registry_key.map(|s| s.to_string()),
-        process_name: lc.process_name.map(|s| s.to_string()),
+        laptop_only: data.laptop_only,
+        kind,
+    };
+
+    // Refuse anything validate-config would reject
+    match check.validate().into_iter().next() {
+        Some(problem) => Err(problem),
+        None => Ok(check),
     }
 }
 
@@ -441,12 +515,36 @@ fn parse_check_type(s: &str) -> CheckType {
         "PowerScheme" => CheckType::PowerScheme,
         "PowerMode" => CheckType::PowerMode,
         "RegistryDword" => CheckType::RegistryDword,
+        "RegistryQword" => CheckType::RegistryQword,
         "RegistryString" => CheckType::RegistryString,
+        "RegistryExpandString" => CheckType::RegistryExpandString,
+        "RegistryMultiString" => CheckType::RegistryMultiString,
+        "RegistryBinary" => CheckType::RegistryBinary,
+        "RegistryExists" => CheckType::RegistryExists,
+        "RegistryAbsent" => CheckType::RegistryAbsent,
         "ProcessAbsent" => CheckType::ProcessAbsent,
         "ProcessPresent" => CheckType::ProcessPresent,
         "DisplayResolution" => CheckType::DisplayResolution,
         "DisplayRefreshRate" => CheckType::DisplayRefreshRate,
+        "DisplayCount" => CheckType::DisplayCount,
+        "DisplayBitDepth" => CheckType::DisplayBitDepth,
+        "DisplayScaling" => CheckType::DisplayScaling,
+        "DisplayOrientation" => CheckType::DisplayOrientation,
         "HdrEnabled" => CheckType::HdrEnabled,
+        "PowerSetting" => CheckType::PowerSetting,
+        "ServiceState" => CheckType::ServiceState,
+        "TaskDisabled" => CheckType::TaskDisabled,
+        "TaskNotRunning" => CheckType::TaskNotRunning,
+        "AcPower" => CheckType::AcPower,
+        "BatteryLevel" => CheckType::BatteryLevel,
+        "CpuIdle" => CheckType::CpuIdle,
+        "MemoryAvailable" => CheckType::MemoryAvailable,
+        "CommitCharge" => CheckType::CommitCharge,
+        "Pagefile" => CheckType::Pagefile,
+        "DiskFree" => CheckType::DiskFree,
+        "DriveType" => CheckType::DriveType,
+        "PendingReboot" => CheckType::PendingReboot,
+        "WindowsUpdateActive" => CheckType::WindowsUpdateActive,
         _ => CheckType::PowerScheme, // Default fallback
     }
 }
@@ -1,5 +1,5 @@
 use crate::autostart;
-use crate::checkers::OverallStatus;
+use bench_checklist_core::checkers::OverallStatus;
 use tray_icon::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem};
 use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
 